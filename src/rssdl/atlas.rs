@@ -0,0 +1,443 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+    path::Path,
+};
+
+use anyhow;
+
+use crate::{core::Rect, renderer::Renderer, rwops::RWops, sprite::SpriteSheet, surface::Surface, textures::Texture};
+
+/// Skyline bottom-left rectangle packer for a fixed size bin.
+pub struct SkylinePacker {
+    width: i32,
+    height: i32,
+    /// Top edge of the used area as `(x, y, width)` segments, left to right.
+    skyline: Vec<(i32, i32, i32)>,
+}
+
+impl SkylinePacker {
+    pub fn new(width: i32, height: i32) -> SkylinePacker {
+        SkylinePacker { width, height, skyline: vec![(0, 0, width)] }
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    /// The y a `width` x `height` rect gets when its left edge sits on segment `index`.
+    fn fit(&self, index: usize, width: i32, height: i32) -> Option<i32> {
+        let x = self.skyline[index].0;
+        if x + width > self.width {
+            return None;
+        }
+
+        let mut y = 0;
+        let mut left = width;
+        let mut i = index;
+        while left > 0 {
+            let (_, segment_y, segment_width) = self.skyline[i];
+            y = y.max(segment_y);
+            if y + height > self.height {
+                return None;
+            }
+            left -= segment_width;
+            i += 1;
+        }
+
+        return Some(y);
+    }
+
+    /// Places a `width` x `height` rect as low as possible, `None` if the bin is full.
+    pub fn insert(&mut self, width: i32, height: i32) -> Option<Rect> {
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+
+        // lowest top edge first, then the narrowest segment to waste less space
+        let mut best: Option<(usize, i32)> = None;
+        for index in 0..self.skyline.len() {
+            if let Some(y) = self.fit(index, width, height) {
+                let better = match best {
+                    None => true,
+                    Some((best_index, best_y)) => y < best_y || (y == best_y && self.skyline[index].2 < self.skyline[best_index].2),
+                };
+                if better {
+                    best = Some((index, y));
+                }
+            }
+        }
+        let (index, y) = best?;
+        let x = self.skyline[index].0;
+
+        self.skyline.insert(index, (x, y + height, width));
+        let right = x + width;
+        let i = index + 1;
+        while i < self.skyline.len() && self.skyline[i].0 < right {
+            let (segment_x, segment_y, segment_width) = self.skyline[i];
+            let segment_right = segment_x + segment_width;
+            if segment_right <= right {
+                self.skyline.remove(i);
+            } else {
+                self.skyline[i] = (right, segment_y, segment_right - right);
+                break;
+            }
+        }
+
+        let mut i = 0;
+        while i + 1 < self.skyline.len() {
+            if self.skyline[i].1 == self.skyline[i + 1].1 {
+                self.skyline[i].2 += self.skyline[i + 1].2;
+                self.skyline.remove(i + 1);
+            } else {
+                i += 1;
+            }
+        }
+
+        return Some(Rect::new(x, y, width, height));
+    }
+}
+
+/// Collects named surfaces and packs them into one atlas, so drawing them needs a single texture
+/// and the renderer can batch the copies.
+///
+/// ```ignore
+/// let mut builder = AtlasBuilder::new();
+/// builder.padding(1);
+/// builder.add_image("player", "assets/player.png\0")?;
+/// builder.add_image("coin", "assets/coin.png\0")?;
+/// let packed = builder.build()?;
+/// packed.save_png("atlas.png\0")?;
+/// packed.save_manifest(&mut RWops::from_file("atlas.json\0", "wb\0")?, "atlas.png")?;
+/// let atlas = packed.to_texture(&window.renderer)?;
+/// window.renderer.copy(&atlas.texture, atlas.rect("coin").unwrap(), dst);
+/// ```
+pub struct AtlasBuilder {
+    sprites: Vec<(String, Surface)>,
+    padding: i32,
+    max_size: i32,
+}
+
+impl AtlasBuilder {
+    pub fn new() -> AtlasBuilder {
+        AtlasBuilder { sprites: Vec::new(), padding: 0, max_size: 4096 }
+    }
+
+    /// Empty pixels between sprites, avoids bleeding when scaling with linear filtering.
+    pub fn padding(&mut self, pixels: i32) -> &mut AtlasBuilder {
+        self.padding = pixels.max(0);
+        self
+    }
+
+    /// Largest width and height the atlas may grow to, keep it within the renderer's max texture size.
+    pub fn max_size(&mut self, pixels: i32) -> &mut AtlasBuilder {
+        self.max_size = pixels;
+        self
+    }
+
+    pub fn add(&mut self, name: &str, surface: Surface) -> &mut AtlasBuilder {
+        self.sprites.push((name.to_owned(), surface));
+        self
+    }
+
+    /// Loads any format supported by SDL2_image, see `Surface::load_image`.
+    pub fn add_image<P: AsRef<Path>>(&mut self, name: &str, path: P) -> anyhow::Result<&mut AtlasBuilder> {
+        let surface = Surface::load_image(path)?;
+
+        return Ok(self.add(name, surface));
+    }
+
+    /// Packs the sprites into the smallest power of two atlas that fits them.
+    pub fn build(&self) -> anyhow::Result<AtlasSurface> {
+        let mut names = HashSet::with_capacity(self.sprites.len());
+        for (name, _) in self.sprites.iter() {
+            if !names.insert(name.as_str()) {
+                return Err(anyhow::anyhow!("Sprite {:?} added twice {}({},{})", name, file!(), line!(), column!()));
+            }
+        }
+
+        let sizes: Vec<(i32, i32)> = self.sprites.iter().map(|(_, surface)| (surface.width() + self.padding, surface.height() + self.padding)).collect();
+        let (width, height, placed) = pack(&sizes, self.max_size)?;
+
+        let mut surface = Surface::new(width, height)?;
+        let mut rects = HashMap::with_capacity(placed.len());
+        for ((name, sprite), rect) in self.sprites.iter().zip(placed) {
+            sprite.blit(None, &mut surface, rect.raw.x, rect.raw.y, false)?;
+            rects.insert(name.clone(), Rect::new(rect.raw.x, rect.raw.y, sprite.width(), sprite.height()));
+        }
+
+        return Ok(AtlasSurface { surface, rects });
+    }
+}
+
+/// Packs `width` x `height` rects into the smallest power of two bin, growing the shorter side
+/// first. Returns the bin size and the rects in the order of `sizes`.
+fn pack(sizes: &[(i32, i32)], max_size: i32) -> anyhow::Result<(i32, i32, Vec<Rect>)> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| (std::cmp::Reverse(sizes[i].1), std::cmp::Reverse(sizes[i].0)));
+
+    let area: f64 = sizes.iter().map(|&(w, h)| w as f64 * h as f64).sum();
+    let widest = sizes.iter().map(|&(w, _)| w).max().unwrap_or(1).max(1);
+
+    let dont_fit = || anyhow::anyhow!("The sprites don't fit in a {}x{} atlas {}({},{})", max_size, max_size, file!(), line!(), column!());
+
+    // the sizes near i32::MAX have no power of two left to grow into
+    let side = (widest as u32).max(area.sqrt().ceil() as u32).checked_next_power_of_two();
+    let mut width = match side.and_then(|side| i32::try_from(side).ok()) {
+        Some(side) => side,
+        None => return Err(dont_fit()),
+    };
+    let mut height = width;
+    loop {
+        if width > max_size || height > max_size {
+            return Err(dont_fit());
+        }
+
+        let mut packer = SkylinePacker::new(width, height);
+        let mut placed = vec![Rect::new(0, 0, 0, 0); sizes.len()];
+        let fits = order.iter().all(|&i| match packer.insert(sizes[i].0, sizes[i].1) {
+            Some(rect) => {
+                placed[i] = rect;
+                true
+            }
+            None => false,
+        });
+
+        if fits {
+            return Ok((width, height, placed));
+        }
+        let grown = if height < width { &mut height } else { &mut width };
+        *grown = match grown.checked_mul(2) {
+            Some(size) => size,
+            None => return Err(dont_fit()),
+        };
+    }
+}
+
+/// A packed atlas still in memory, to save or to upload with `to_texture`.
+pub struct AtlasSurface {
+    pub surface: Surface,
+    pub rects: HashMap<String, Rect>,
+}
+
+impl AtlasSurface {
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn rect(&self, name: &str) -> Option<Rect> {
+        self.rects.get(name).copied()
+    }
+
+    #[doc(alias = "SDL_CreateTextureFromSurface")]
+    pub fn to_texture(&self, renderer: &Renderer) -> anyhow::Result<Atlas> {
+        let texture = renderer.create_texture_from_surface(&self.surface)?;
+
+        return Ok(Atlas { texture, rects: self.rects.clone() });
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
+        self.surface.save_png(path)
+    }
+
+    /// Writes the rects as TexturePacker "JSON (Hash)", `image` is the file name of the saved png.
+    /// `SpriteSheet::load_json` reads it back.
+    pub fn save_manifest(&self, rwops: &mut RWops, image: &str) -> anyhow::Result<()> {
+        let mut names: Vec<&String> = self.rects.keys().collect();
+        names.sort();
+
+        let mut json = String::from("{\"frames\": {\n");
+        for (i, name) in names.iter().enumerate() {
+            let r = self.rects[*name].raw;
+            json += &format!(
+                "  {}: {{\"frame\": {{\"x\": {}, \"y\": {}, \"w\": {}, \"h\": {}}}, \"rotated\": false, \"trimmed\": false, \"spriteSourceSize\": {{\"x\": 0, \"y\": 0, \"w\": {}, \"h\": {}}}, \"sourceSize\": {{\"w\": {}, \"h\": {}}}}}{}\n",
+                json_string(name),
+                r.x,
+                r.y,
+                r.w,
+                r.h,
+                r.w,
+                r.h,
+                r.w,
+                r.h,
+                if i + 1 < names.len() { "," } else { "" }
+            );
+        }
+        json += &format!(
+            "}},\n\"meta\": {{\"image\": {}, \"format\": \"RGBA8888\", \"size\": {{\"w\": {}, \"h\": {}}}, \"scale\": \"1\"}}}}\n",
+            json_string(image),
+            self.surface.width(),
+            self.surface.height()
+        );
+
+        if let Err(ioerror) = rwops.write_all(json.as_bytes()) {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", ioerror, file!(), line!(), column!()));
+        }
+
+        return Ok(());
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+
+    return out;
+}
+
+/// A packed atlas uploaded to the renderer.
+pub struct Atlas {
+    pub texture: Texture,
+    pub rects: HashMap<String, Rect>,
+}
+
+impl Atlas {
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn rect(&self, name: &str) -> Option<Rect> {
+        self.rects.get(name).copied()
+    }
+
+    /// A sheet with one named frame per sprite, in name order.
+    pub fn into_sprite_sheet(self) -> SpriteSheet {
+        let mut rects: Vec<(String, Rect)> = self.rects.into_iter().collect();
+        rects.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut sheet = SpriteSheet::new(self.texture);
+        for (name, rect) in rects {
+            sheet.add_named_frame(&name, rect);
+        }
+
+        return sheet;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sdl2_sys as sdl;
+
+    use super::*;
+
+    fn overlaps(a: &Rect, b: &Rect) -> bool {
+        let (a, b) = (a.raw, b.raw);
+        a.x < b.x + b.w && b.x < a.x + a.w && a.y < b.y + b.h && b.y < a.y + a.h
+    }
+
+    fn assert_packed(width: i32, height: i32, rects: &[Rect]) {
+        for (i, rect) in rects.iter().enumerate() {
+            let r = rect.raw;
+            assert!(r.x >= 0 && r.y >= 0 && r.x + r.w <= width && r.y + r.h <= height, "{:?} outside {}x{}", (r.x, r.y, r.w, r.h), width, height);
+            for other in rects[i + 1..].iter() {
+                assert!(!overlaps(rect, other), "{:?} overlaps {:?}", (r.x, r.y, r.w, r.h), (other.raw.x, other.raw.y, other.raw.w, other.raw.h));
+            }
+        }
+    }
+
+    /// Deterministic mix of small and large sizes.
+    fn sizes(count: usize) -> Vec<(i32, i32)> {
+        let mut state = 0x2545_f491u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+
+        return (0..count).map(|_| ((next() % 40 + 1) as i32, (next() % 40 + 1) as i32)).collect();
+    }
+
+    #[test]
+    fn packer_fills_the_bin_without_overlaps() {
+        let mut packer = SkylinePacker::new(128, 128);
+        let mut rects = Vec::new();
+        for (w, h) in sizes(200) {
+            if let Some(rect) = packer.insert(w, h) {
+                assert_eq!((rect.raw.w, rect.raw.h), (w, h));
+                rects.push(rect);
+            }
+        }
+
+        assert!(rects.len() > 10);
+        assert_packed(128, 128, &rects);
+    }
+
+    #[test]
+    fn packer_rejects_empty_and_oversized_rects() {
+        let mut packer = SkylinePacker::new(64, 64);
+
+        assert!(packer.insert(0, 10).is_none());
+        assert!(packer.insert(10, -1).is_none());
+        assert!(packer.insert(65, 1).is_none());
+        assert!(packer.insert(1, 65).is_none());
+        assert!(packer.insert(64, 64).is_some());
+        assert!(packer.insert(1, 1).is_none());
+    }
+
+    #[test]
+    fn pack_keeps_the_input_order() {
+        let sizes = sizes(60);
+        let (width, height, rects) = pack(&sizes, 4096).unwrap();
+
+        assert!(width.count_ones() == 1 && height.count_ones() == 1);
+        assert_eq!(rects.len(), sizes.len());
+        for (rect, (w, h)) in rects.iter().zip(sizes.iter()) {
+            assert_eq!((rect.raw.w, rect.raw.h), (*w, *h));
+        }
+        assert_packed(width, height, &rects);
+    }
+
+    #[test]
+    fn pack_grows_by_powers_of_two() {
+        // starts at 16x16 from the area, then doubles the shorter side until the height fits
+        let (width, height, rects) = pack(&[(1, 100), (1, 100)], 4096).unwrap();
+
+        assert_eq!((width, height), (128, 128));
+        assert_packed(width, height, &rects);
+
+        assert_eq!(pack(&[(100, 1)], 4096).unwrap().0, 128);
+        assert_eq!(pack(&[(64, 64); 4], 4096).unwrap().0, 128);
+        assert_eq!(pack(&[], 4096).unwrap().0, 1);
+    }
+
+    #[test]
+    fn pack_stops_at_max_size() {
+        assert!(pack(&[(64, 64)], 64).is_ok());
+        assert!(pack(&[(65, 1)], 64).is_err());
+        assert!(pack(&[(1, 100)], 64).is_err());
+        assert!(pack(&[(64, 64); 2], 64).is_err());
+    }
+
+    #[test]
+    fn pack_does_not_overflow_near_i32_max() {
+        assert!(pack(&[(1 << 30, 1 << 30); 2], i32::MAX).is_err());
+        assert!(pack(&[(i32::MAX, 1)], i32::MAX).is_err());
+        assert!(pack(&[(i32::MAX, i32::MAX); 3], i32::MAX).is_err());
+    }
+
+    #[test]
+    fn build_leaves_the_sprites_blend_mode_alone() {
+        let sprite = Surface::new(10, 20).unwrap();
+        unsafe { sdl::SDL_SetSurfaceBlendMode(sprite.raw, sdl::SDL_BlendMode::SDL_BLENDMODE_ADD) };
+
+        let mut builder = AtlasBuilder::new();
+        builder.padding(1).add("tall", sprite).add("wide", Surface::new(30, 5).unwrap());
+        let packed = builder.build().unwrap();
+
+        assert_eq!((packed.surface.width(), packed.surface.height()), (32, 32));
+        let (tall, wide) = (packed.rect("tall").unwrap(), packed.rect("wide").unwrap());
+        assert_eq!((tall.raw.w, tall.raw.h, wide.raw.w, wide.raw.h), (10, 20, 30, 5));
+        assert!(!overlaps(&tall, &wide));
+
+        let mut mode = sdl::SDL_BlendMode::SDL_BLENDMODE_NONE;
+        unsafe { sdl::SDL_GetSurfaceBlendMode(builder.sprites[0].1.raw, &mut mode) };
+        assert!(mode == sdl::SDL_BlendMode::SDL_BLENDMODE_ADD);
+    }
+}
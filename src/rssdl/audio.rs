@@ -0,0 +1,1014 @@
+use std::{
+    ffi::{c_void, CString},
+    io::Write,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    path::Path,
+};
+
+use anyhow;
+use sdl2_sys as sdl;
+
+use crate::rwops::RWops;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum AudioFormat {
+    U8,
+    S8,
+    U16LSB,
+    U16MSB,
+    S16LSB,
+    S16MSB,
+    S32LSB,
+    S32MSB,
+    F32LSB,
+    F32MSB,
+}
+
+impl AudioFormat {
+    pub fn from_ll(format: sdl::SDL_AudioFormat) -> Option<AudioFormat> {
+        let format = match format as u32 {
+            sdl::AUDIO_U8 => AudioFormat::U8,
+            sdl::AUDIO_S8 => AudioFormat::S8,
+            sdl::AUDIO_U16LSB => AudioFormat::U16LSB,
+            sdl::AUDIO_U16MSB => AudioFormat::U16MSB,
+            sdl::AUDIO_S16LSB => AudioFormat::S16LSB,
+            sdl::AUDIO_S16MSB => AudioFormat::S16MSB,
+            sdl::AUDIO_S32LSB => AudioFormat::S32LSB,
+            sdl::AUDIO_S32MSB => AudioFormat::S32MSB,
+            sdl::AUDIO_F32LSB => AudioFormat::F32LSB,
+            sdl::AUDIO_F32MSB => AudioFormat::F32MSB,
+            _ => return None,
+        };
+
+        return Some(format);
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn to_ll(self) -> sdl::SDL_AudioFormat {
+        let format = match self {
+            AudioFormat::U8 => sdl::AUDIO_U8,
+            AudioFormat::S8 => sdl::AUDIO_S8,
+            AudioFormat::U16LSB => sdl::AUDIO_U16LSB,
+            AudioFormat::U16MSB => sdl::AUDIO_U16MSB,
+            AudioFormat::S16LSB => sdl::AUDIO_S16LSB,
+            AudioFormat::S16MSB => sdl::AUDIO_S16MSB,
+            AudioFormat::S32LSB => sdl::AUDIO_S32LSB,
+            AudioFormat::S32MSB => sdl::AUDIO_S32MSB,
+            AudioFormat::F32LSB => sdl::AUDIO_F32LSB,
+            AudioFormat::F32MSB => sdl::AUDIO_F32MSB,
+        };
+
+        return format as sdl::SDL_AudioFormat;
+    }
+
+    /// Native endian signed 16 bit.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn s16_sys() -> AudioFormat {
+        if cfg!(target_endian = "little") { AudioFormat::S16LSB } else { AudioFormat::S16MSB }
+    }
+
+    /// Native endian signed 32 bit.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn s32_sys() -> AudioFormat {
+        if cfg!(target_endian = "little") { AudioFormat::S32LSB } else { AudioFormat::S32MSB }
+    }
+
+    /// Native endian 32 bit float.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn f32_sys() -> AudioFormat {
+        if cfg!(target_endian = "little") { AudioFormat::F32LSB } else { AudioFormat::F32MSB }
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn bytes_per_sample(self) -> usize {
+        (self.to_ll() as u32 & sdl::SDL_AUDIO_MASK_BITSIZE) as usize / 8
+    }
+}
+
+/// A sample type SDL can play without conversion, in native endianness.
+pub trait AudioSample: Copy + Send + 'static {
+    const FORMAT: AudioFormat;
+    const SILENCE: Self;
+}
+
+impl AudioSample for u8 {
+    const FORMAT: AudioFormat = AudioFormat::U8;
+    const SILENCE: u8 = 0x80;
+}
+
+impl AudioSample for i8 {
+    const FORMAT: AudioFormat = AudioFormat::S8;
+    const SILENCE: i8 = 0;
+}
+
+impl AudioSample for i16 {
+    const FORMAT: AudioFormat = AudioFormat::s16_sys();
+    const SILENCE: i16 = 0;
+}
+
+impl AudioSample for i32 {
+    const FORMAT: AudioFormat = AudioFormat::s32_sys();
+    const SILENCE: i32 = 0;
+}
+
+impl AudioSample for f32 {
+    const FORMAT: AudioFormat = AudioFormat::f32_sys();
+    const SILENCE: f32 = 0.0;
+}
+
+/// Sample rate, format and layout of an audio device or buffer.
+///
+/// Samples of the channels are interleaved, `samples` is the device buffer size in
+/// sample frames and should be a power of two.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct AudioSpec {
+    pub freq: i32,
+    pub format: AudioFormat,
+    pub channels: u8,
+    pub samples: u16,
+}
+
+impl AudioSpec {
+    pub fn new(freq: i32, format: AudioFormat, channels: u8, samples: u16) -> AudioSpec {
+        AudioSpec { freq, format, channels, samples }
+    }
+
+    pub fn from_ll(spec: &sdl::SDL_AudioSpec) -> anyhow::Result<AudioSpec> {
+        match AudioFormat::from_ll(spec.format) {
+            Some(format) => Ok(AudioSpec { freq: spec.freq, format, channels: spec.channels, samples: spec.samples }),
+            None => Err(anyhow::anyhow!("Unsupported audio format {:#x} {}({},{})", spec.format, file!(), line!(), column!())),
+        }
+    }
+
+    pub fn to_ll(&self) -> sdl::SDL_AudioSpec {
+        let mut spec: sdl::SDL_AudioSpec = unsafe { std::mem::zeroed() };
+        spec.freq = self.freq;
+        spec.format = self.format.to_ll();
+        spec.channels = self.channels;
+        spec.samples = self.samples;
+        return spec;
+    }
+
+    /// Size in bytes of one sample frame, i.e. one sample for every channel.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn frame_size(&self) -> usize {
+        self.format.bytes_per_sample() * self.channels as usize
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum AudioStatus {
+    Stopped,
+    Playing,
+    Paused,
+}
+
+impl AudioStatus {
+    pub fn from_ll(status: sdl::SDL_AudioStatus) -> AudioStatus {
+        match status {
+            sdl::SDL_AudioStatus::SDL_AUDIO_STOPPED => AudioStatus::Stopped,
+            sdl::SDL_AudioStatus::SDL_AUDIO_PLAYING => AudioStatus::Playing,
+            sdl::SDL_AudioStatus::SDL_AUDIO_PAUSED => AudioStatus::Paused,
+        }
+    }
+}
+
+/// Initializes the audio subsystem.
+///
+/// The driver can be picked with the `SDL_AUDIODRIVER` environment variable, e.g.
+/// `dummy` or `disk` (plays to `sdlaudio.raw`, records from `sdlaudio-in.raw`) to run
+/// without a sound card.
+pub struct AudioSubsystem;
+
+impl Drop for AudioSubsystem {
+    fn drop(&mut self) {
+        unsafe {
+            sdl::SDL_QuitSubSystem(sdl::SDL_INIT_AUDIO);
+        }
+    }
+}
+
+impl AudioSubsystem {
+    pub fn new() -> anyhow::Result<AudioSubsystem> {
+        let res = unsafe { sdl::SDL_InitSubSystem(sdl::SDL_INIT_AUDIO) };
+
+        if res != 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(AudioSubsystem {});
+    }
+
+    #[doc(alias = "SDL_GetCurrentAudioDriver")]
+    pub fn current_driver(&self) -> String {
+        unsafe {
+            let name = sdl::SDL_GetCurrentAudioDriver();
+            if name.is_null() {
+                return String::new();
+            }
+            return std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned();
+        }
+    }
+
+    /// Number of playback devices, `None` if they can't be listed (open the default device instead).
+    #[doc(alias = "SDL_GetNumAudioDevices")]
+    pub fn num_playback_devices(&self) -> Option<u32> {
+        let count = unsafe { sdl::SDL_GetNumAudioDevices(0) };
+        if count < 0 {
+            return None;
+        }
+        return Some(count as u32);
+    }
+
+    #[doc(alias = "SDL_GetAudioDeviceName")]
+    pub fn playback_device_name(&self, index: u32) -> anyhow::Result<String> {
+        return device_name(index, false);
+    }
+
+    /// Opens a playback device that calls `callback` from the audio thread whenever it
+    /// needs samples. `device` is a name from `playback_device_name`, `None` for the default.
+    ///
+    /// The device starts paused, call `resume` to start playback. SDL converts to the
+    /// hardware format, so the callback always gets `spec`.
+    #[doc(alias = "SDL_OpenAudioDevice")]
+    pub fn open_playback<CB: AudioCallback>(&self, device: Option<&str>, spec: &AudioSpec, callback: CB) -> anyhow::Result<AudioDevice<'_, CB>> {
+        return AudioDevice::open(device, false, spec, callback);
+    }
+
+    /// Opens a playback device that plays the samples pushed with `AudioQueue::queue_audio`.
+    #[doc(alias = "SDL_OpenAudioDevice")]
+    pub fn open_queue<S: AudioSample>(&self, device: Option<&str>, spec: &AudioSpec) -> anyhow::Result<AudioQueue<'_, S>> {
+        return AudioQueue::open(device, false, spec);
+    }
+
+    /// Number of capture devices (microphones), `None` if they can't be listed.
+    #[doc(alias = "SDL_GetNumAudioDevices")]
+    pub fn num_capture_devices(&self) -> Option<u32> {
+        let count = unsafe { sdl::SDL_GetNumAudioDevices(1) };
+        if count < 0 {
+            return None;
+        }
+        return Some(count as u32);
+    }
+
+    #[doc(alias = "SDL_GetAudioDeviceName")]
+    pub fn capture_device_name(&self, index: u32) -> anyhow::Result<String> {
+        return device_name(index, true);
+    }
+
+    /// Opens a capture device that hands the recorded samples to `callback` from the audio thread.
+    ///
+    /// The device starts paused, call `resume` to start recording.
+    #[doc(alias = "SDL_OpenAudioDevice")]
+    pub fn open_capture<CB: AudioCallback>(&self, device: Option<&str>, spec: &AudioSpec, callback: CB) -> anyhow::Result<AudioDevice<'_, CB>> {
+        return AudioDevice::open(device, true, spec, callback);
+    }
+
+    /// Opens a capture device whose samples are read with `AudioQueue::dequeue_audio`.
+    #[doc(alias = "SDL_OpenAudioDevice")]
+    pub fn open_capture_queue<S: AudioSample>(&self, device: Option<&str>, spec: &AudioSpec) -> anyhow::Result<AudioQueue<'_, S>> {
+        return AudioQueue::open(device, true, spec);
+    }
+}
+
+fn device_name(index: u32, iscapture: bool) -> anyhow::Result<String> {
+    let name = unsafe { sdl::SDL_GetAudioDeviceName(index as i32, iscapture as i32) };
+
+    if name.is_null() {
+        return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+    }
+
+    return Ok(unsafe { std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned() });
+}
+
+/// Opens a device that converts from/to `spec`, `spec.format` must be the one of `S`.
+///
+/// Returns the device and the spec SDL obtained, `samples` may differ from the desired one.
+unsafe fn open_device<S: AudioSample>(device: Option<&str>, iscapture: bool, spec: &AudioSpec, callback: sdl::SDL_AudioCallback, userdata: *mut c_void) -> anyhow::Result<(sdl::SDL_AudioDeviceID, AudioSpec)> {
+    if spec.format != S::FORMAT {
+        return Err(anyhow::anyhow!("Audio format {:?} does not match the sample type {:?} {}({},{})", spec.format, S::FORMAT, file!(), line!(), column!()));
+    }
+
+    let device = match device {
+        Some(device) => Some(CString::new(device)?),
+        None => None,
+    };
+    let device_ptr = device.as_ref().map_or(std::ptr::null(), |d| d.as_ptr());
+
+    let mut desired = spec.to_ll();
+    desired.callback = callback;
+    desired.userdata = userdata;
+    let mut obtained: sdl::SDL_AudioSpec = std::mem::zeroed();
+
+    let id = sdl::SDL_OpenAudioDevice(device_ptr, iscapture as i32, &desired, &mut obtained, 0);
+    if id == 0 {
+        return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+    }
+
+    return match AudioSpec::from_ll(&obtained) {
+        Ok(obtained) => Ok((id, obtained)),
+        Err(e) => {
+            sdl::SDL_CloseAudioDevice(id);
+            Err(e)
+        }
+    };
+}
+
+/// Produces (or, for capture devices, consumes) the samples of an `AudioDevice`.
+///
+/// Runs on a thread managed by SDL while the device is unpaused. The callback *must* not
+/// panic and should not block, use `AudioDevice::lock` to share state with it.
+pub trait AudioCallback: Send {
+    type Sample: AudioSample;
+
+    /// `samples` holds `spec.samples * spec.channels` interleaved samples.
+    fn callback(&mut self, samples: &mut [Self::Sample]);
+}
+
+/// An open audio device driven by an `AudioCallback`, closed on drop.
+pub struct AudioDevice<'a, CB: AudioCallback> {
+    id: sdl::SDL_AudioDeviceID,
+    spec: AudioSpec,
+    // boxed so the address handed to SDL stays the same when the device is moved
+    callback: Option<Box<CB>>,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a, CB: AudioCallback> Drop for AudioDevice<'a, CB> {
+    #[doc(alias = "SDL_CloseAudioDevice")]
+    fn drop(&mut self) {
+        // waits for a running callback, so the box is not in use anymore afterwards
+        if self.id != 0 {
+            unsafe { sdl::SDL_CloseAudioDevice(self.id) };
+        }
+    }
+}
+
+impl<'a, CB: AudioCallback> AudioDevice<'a, CB> {
+    pub(crate) fn open(device: Option<&str>, iscapture: bool, spec: &AudioSpec, callback: CB) -> anyhow::Result<AudioDevice<'a, CB>> {
+        let mut callback = Box::new(callback);
+        let userdata = &mut *callback as *mut CB as *mut c_void;
+        let (id, spec) = unsafe { open_device::<CB::Sample>(device, iscapture, spec, Some(c_audio_callback::<CB>), userdata)? };
+
+        return Ok(AudioDevice { id, spec, callback: Some(callback), _marker: PhantomData });
+    }
+
+    /// The spec SDL obtained, the buffer size in `samples` may differ from the requested one.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn spec(&self) -> &AudioSpec {
+        &self.spec
+    }
+
+    #[doc(alias = "SDL_GetAudioDeviceStatus")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn status(&self) -> AudioStatus {
+        AudioStatus::from_ll(unsafe { sdl::SDL_GetAudioDeviceStatus(self.id) })
+    }
+
+    #[doc(alias = "SDL_PauseAudioDevice")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn pause(&self) {
+        unsafe { sdl::SDL_PauseAudioDevice(self.id, 1) }
+    }
+
+    #[doc(alias = "SDL_PauseAudioDevice")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn resume(&self) {
+        unsafe { sdl::SDL_PauseAudioDevice(self.id, 0) }
+    }
+
+    /// Stops the callback from running until the guard is dropped and gives access to it.
+    #[doc(alias = "SDL_LockAudioDevice")]
+    pub fn lock(&mut self) -> AudioDeviceLockGuard<'_, CB> {
+        unsafe { sdl::SDL_LockAudioDevice(self.id) };
+        let callback = self.callback.as_deref_mut().unwrap();
+        return AudioDeviceLockGuard { id: self.id, callback };
+    }
+
+    /// Closes the device and returns the callback.
+    pub fn close_and_get_callback(mut self) -> CB {
+        unsafe { sdl::SDL_CloseAudioDevice(self.id) };
+        // ids are reused, make sure drop doesn't close a device opened later
+        self.id = 0;
+        return *self.callback.take().unwrap();
+    }
+}
+
+pub struct AudioDeviceLockGuard<'a, CB: AudioCallback> {
+    id: sdl::SDL_AudioDeviceID,
+    callback: &'a mut CB,
+}
+
+impl<'a, CB: AudioCallback> Drop for AudioDeviceLockGuard<'a, CB> {
+    #[doc(alias = "SDL_UnlockAudioDevice")]
+    fn drop(&mut self) {
+        unsafe { sdl::SDL_UnlockAudioDevice(self.id) };
+    }
+}
+
+impl<'a, CB: AudioCallback> Deref for AudioDeviceLockGuard<'a, CB> {
+    type Target = CB;
+
+    fn deref(&self) -> &CB {
+        self.callback
+    }
+}
+
+impl<'a, CB: AudioCallback> DerefMut for AudioDeviceLockGuard<'a, CB> {
+    fn deref_mut(&mut self) -> &mut CB {
+        self.callback
+    }
+}
+
+extern "C" fn c_audio_callback<CB: AudioCallback>(userdata: *mut c_void, stream: *mut u8, len: i32) {
+    // FIXME: Same as `c_timer_callback`, this is UB if the callback panics!
+    let callback = userdata as *mut CB;
+    unsafe {
+        let samples = std::slice::from_raw_parts_mut(stream as *mut CB::Sample, len as usize / std::mem::size_of::<CB::Sample>());
+        (*callback).callback(samples);
+    }
+}
+
+/// An open audio device without callback, samples are pushed (or, for capture devices,
+/// pulled) from the calling thread. Closed on drop.
+pub struct AudioQueue<'a, S: AudioSample> {
+    id: sdl::SDL_AudioDeviceID,
+    spec: AudioSpec,
+    _marker: PhantomData<&'a S>,
+}
+
+impl<'a, S: AudioSample> Drop for AudioQueue<'a, S> {
+    #[doc(alias = "SDL_CloseAudioDevice")]
+    fn drop(&mut self) {
+        unsafe { sdl::SDL_CloseAudioDevice(self.id) };
+    }
+}
+
+impl<'a, S: AudioSample> AudioQueue<'a, S> {
+    pub(crate) fn open(device: Option<&str>, iscapture: bool, spec: &AudioSpec) -> anyhow::Result<AudioQueue<'a, S>> {
+        let (id, spec) = unsafe { open_device::<S>(device, iscapture, spec, None, std::ptr::null_mut())? };
+
+        return Ok(AudioQueue { id, spec, _marker: PhantomData });
+    }
+
+    /// The spec SDL obtained, see `AudioDevice::spec`.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn spec(&self) -> &AudioSpec {
+        &self.spec
+    }
+
+    #[doc(alias = "SDL_GetAudioDeviceStatus")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn status(&self) -> AudioStatus {
+        AudioStatus::from_ll(unsafe { sdl::SDL_GetAudioDeviceStatus(self.id) })
+    }
+
+    #[doc(alias = "SDL_PauseAudioDevice")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn pause(&self) {
+        unsafe { sdl::SDL_PauseAudioDevice(self.id, 1) }
+    }
+
+    #[doc(alias = "SDL_PauseAudioDevice")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn resume(&self) {
+        unsafe { sdl::SDL_PauseAudioDevice(self.id, 0) }
+    }
+
+    /// Appends interleaved samples to the queue, they are copied.
+    #[doc(alias = "SDL_QueueAudio")]
+    pub fn queue_audio(&self, samples: &[S]) -> anyhow::Result<()> {
+        unsafe {
+            let len = std::mem::size_of_val(samples) as u32;
+            if sdl::SDL_QueueAudio(self.id, samples.as_ptr() as *const c_void, len) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Reads recorded samples of a capture device into `samples`, returns the number of samples read.
+    #[doc(alias = "SDL_DequeueAudio")]
+    pub fn dequeue_audio(&self, samples: &mut [S]) -> usize {
+        let read = unsafe { sdl::SDL_DequeueAudio(self.id, samples.as_mut_ptr() as *mut c_void, std::mem::size_of_val(samples) as u32) };
+        return read as usize / std::mem::size_of::<S>();
+    }
+
+    /// Number of queued bytes not played, or for capture devices not read, yet.
+    #[doc(alias = "SDL_GetQueuedAudioSize")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn size(&self) -> u32 {
+        unsafe { sdl::SDL_GetQueuedAudioSize(self.id) }
+    }
+
+    /// Drops every queued sample.
+    #[doc(alias = "SDL_ClearQueuedAudio")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn clear(&self) {
+        unsafe { sdl::SDL_ClearQueuedAudio(self.id) }
+    }
+}
+
+enum AudioData {
+    /// Allocated by `SDL_LoadWAV_RW`.
+    Wav { buf: *mut u8, len: u32 },
+    Owned(Vec<u8>),
+}
+
+/// Interleaved samples in the format described by `spec`.
+pub struct AudioBuffer {
+    spec: AudioSpec,
+    data: AudioData,
+}
+
+// the WAV data is plain memory owned by the buffer
+unsafe impl Send for AudioBuffer {}
+unsafe impl Sync for AudioBuffer {}
+
+impl Drop for AudioBuffer {
+    #[doc(alias = "SDL_FreeWAV")]
+    fn drop(&mut self) {
+        if let AudioData::Wav { buf, .. } = self.data {
+            unsafe { sdl::SDL_FreeWAV(buf) };
+        }
+    }
+}
+
+impl AudioBuffer {
+    /// Loads a WAV file, e.g. `assets/sine.wav\0`.
+    pub fn load_wav<P: AsRef<Path>>(path: P) -> anyhow::Result<AudioBuffer> {
+        let mut file = RWops::from_file(path, "rb\0")?;
+        return AudioBuffer::load_wav_rw(&mut file);
+    }
+
+    #[doc(alias = "SDL_LoadWAV_RW")]
+    pub fn load_wav_rw(rwops: &mut RWops) -> anyhow::Result<AudioBuffer> {
+        let mut spec: sdl::SDL_AudioSpec = unsafe { std::mem::zeroed() };
+        let mut buf = std::ptr::null_mut();
+        let mut len = 0;
+
+        unsafe {
+            if sdl::SDL_LoadWAV_RW(rwops.raw, 0, &mut spec, &mut buf, &mut len).is_null() {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        let data = AudioData::Wav { buf, len };
+        return match AudioSpec::from_ll(&spec) {
+            Ok(spec) => Ok(AudioBuffer { spec, data }),
+            Err(e) => {
+                unsafe { sdl::SDL_FreeWAV(buf) };
+                Err(e)
+            }
+        };
+    }
+
+    /// Wraps raw bytes in the format of `spec`, the length must be a multiple of `spec.frame_size()`.
+    pub fn from_bytes(spec: AudioSpec, bytes: Vec<u8>) -> anyhow::Result<AudioBuffer> {
+        if spec.freq <= 0 {
+            return Err(anyhow::anyhow!("Invalid sample rate {} {}({},{})", spec.freq, file!(), line!(), column!()));
+        }
+        if spec.frame_size() == 0 || !bytes.len().is_multiple_of(spec.frame_size()) {
+            return Err(anyhow::anyhow!("{} bytes are not a whole number of {} byte frames {}({},{})", bytes.len(), spec.frame_size(), file!(), line!(), column!()));
+        }
+
+        return Ok(AudioBuffer { spec, data: AudioData::Owned(bytes) });
+    }
+
+    /// Copies `samples` into a new buffer, `spec.format` must be the one of `S`.
+    pub fn from_samples<S: AudioSample>(spec: AudioSpec, samples: &[S]) -> anyhow::Result<AudioBuffer> {
+        if spec.format != S::FORMAT {
+            return Err(anyhow::anyhow!("Audio format {:?} does not match the sample type {:?} {}({},{})", spec.format, S::FORMAT, file!(), line!(), column!()));
+        }
+
+        let bytes = unsafe { std::slice::from_raw_parts(samples.as_ptr() as *const u8, std::mem::size_of_val(samples)) };
+        return AudioBuffer::from_bytes(spec, bytes.to_vec());
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn spec(&self) -> &AudioSpec {
+        &self.spec
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        match &self.data {
+            AudioData::Wav { buf, len } => unsafe { std::slice::from_raw_parts(*buf, *len as usize) },
+            AudioData::Owned(bytes) => bytes,
+        }
+    }
+
+    /// The samples as `S`, `None` if `S` is not the buffer format.
+    pub fn samples<S: AudioSample>(&self) -> Option<&[S]> {
+        let bytes = self.bytes();
+        if self.spec.format != S::FORMAT || !(bytes.as_ptr() as usize).is_multiple_of(std::mem::align_of::<S>()) {
+            return None;
+        }
+
+        return Some(unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const S, bytes.len() / std::mem::size_of::<S>()) });
+    }
+
+    /// Number of sample frames.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn frames(&self) -> usize {
+        self.bytes().len() / self.spec.frame_size()
+    }
+
+    /// 0 if the spec has no valid sample rate.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn duration_ms(&self) -> u32 {
+        if self.spec.freq <= 0 {
+            return 0;
+        }
+
+        return (self.frames() as u64 * 1000 / self.spec.freq as u64) as u32;
+    }
+
+    /// Writes the buffer as a WAV file, e.g. to `RWops::from_file("out.wav\0", "wb\0")`.
+    ///
+    /// Supports the little endian and 8 bit formats, the output only depends on the samples.
+    pub fn save_wav(&self, rwops: &mut RWops) -> anyhow::Result<()> {
+        let format_tag: u16 = match self.spec.format {
+            AudioFormat::U8 | AudioFormat::S16LSB | AudioFormat::S32LSB => 1,
+            AudioFormat::F32LSB => 3,
+            format => return Err(anyhow::anyhow!("Can't write {:?} as WAV {}({},{})", format, file!(), line!(), column!())),
+        };
+        let data = self.bytes();
+        let frame_size = self.spec.frame_size() as u32;
+
+        let mut header = Vec::with_capacity(44);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        header.extend_from_slice(b"WAVEfmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&format_tag.to_le_bytes());
+        header.extend_from_slice(&(self.spec.channels as u16).to_le_bytes());
+        header.extend_from_slice(&(self.spec.freq as u32).to_le_bytes());
+        header.extend_from_slice(&(self.spec.freq as u32 * frame_size).to_le_bytes());
+        header.extend_from_slice(&(frame_size as u16).to_le_bytes());
+        header.extend_from_slice(&(self.spec.format.bytes_per_sample() as u16 * 8).to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+
+        if let Err(ioerror) = rwops.write_all(&header).and_then(|_| rwops.write_all(data)) {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", ioerror, file!(), line!(), column!()));
+        }
+
+        return Ok(());
+    }
+
+    /// Converts the samples to the format, channels and rate of `spec`, e.g. to the spec of the device.
+    pub fn convert(&self, spec: &AudioSpec) -> anyhow::Result<AudioBuffer> {
+        let cvt = AudioCVT::new(self.spec.format, self.spec.channels, self.spec.freq, spec.format, spec.channels, spec.freq)?;
+        let bytes = cvt.convert(self.bytes().to_vec())?;
+        return Ok(AudioBuffer { spec: AudioSpec { samples: self.spec.samples, ..*spec }, data: AudioData::Owned(bytes) });
+    }
+}
+
+/// A one shot conversion between two formats, see `AudioStream` for converting a stream in chunks.
+pub struct AudioCVT {
+    pub raw: sdl::SDL_AudioCVT,
+}
+
+impl AudioCVT {
+    #[doc(alias = "SDL_BuildAudioCVT")]
+    pub fn new(src_format: AudioFormat, src_channels: u8, src_rate: i32, dst_format: AudioFormat, dst_channels: u8, dst_rate: i32) -> anyhow::Result<AudioCVT> {
+        let mut raw: sdl::SDL_AudioCVT = unsafe { std::mem::zeroed() };
+        let res = unsafe { sdl::SDL_BuildAudioCVT(&mut raw, src_format.to_ll(), src_channels, src_rate, dst_format.to_ll(), dst_channels, dst_rate) };
+
+        if res < 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(AudioCVT { raw });
+    }
+
+    /// False if source and destination are the same and `convert` returns its input.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_needed(&self) -> bool {
+        self.raw.needed != 0
+    }
+
+    #[doc(alias = "SDL_ConvertAudio")]
+    pub fn convert(&self, mut bytes: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        if !self.is_needed() {
+            return Ok(bytes);
+        }
+
+        let len = bytes.len();
+        // SDL converts in place and needs len * len_mult bytes of room
+        bytes.resize(len * self.raw.len_mult.max(1) as usize, 0);
+
+        let mut raw = self.raw;
+        raw.buf = bytes.as_mut_ptr();
+        raw.len = len as i32;
+        unsafe {
+            if sdl::SDL_ConvertAudio(&mut raw) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        bytes.truncate(raw.len_cvt as usize);
+        return Ok(bytes);
+    }
+}
+
+/// Converts audio pushed in chunks of any size, e.g. while decoding or capturing.
+pub struct AudioStream {
+    raw: *mut sdl::SDL_AudioStream,
+}
+
+impl Drop for AudioStream {
+    #[doc(alias = "SDL_FreeAudioStream")]
+    fn drop(&mut self) {
+        unsafe { sdl::SDL_FreeAudioStream(self.raw) };
+    }
+}
+
+impl AudioStream {
+    #[doc(alias = "SDL_NewAudioStream")]
+    pub fn new(src_format: AudioFormat, src_channels: u8, src_rate: i32, dst_format: AudioFormat, dst_channels: u8, dst_rate: i32) -> anyhow::Result<AudioStream> {
+        let raw = unsafe { sdl::SDL_NewAudioStream(src_format.to_ll(), src_channels, src_rate, dst_format.to_ll(), dst_channels, dst_rate) };
+
+        if raw.is_null() {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(AudioStream { raw });
+    }
+
+    /// Pushes source bytes, a whole number of source frames.
+    #[doc(alias = "SDL_AudioStreamPut")]
+    pub fn put(&mut self, bytes: &[u8]) -> anyhow::Result<()> {
+        unsafe {
+            if sdl::SDL_AudioStreamPut(self.raw, bytes.as_ptr() as *const c_void, bytes.len() as i32) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Reads converted bytes into `buf`, returns the number of bytes read.
+    #[doc(alias = "SDL_AudioStreamGet")]
+    pub fn get(&mut self, buf: &mut [u8]) -> anyhow::Result<usize> {
+        let read = unsafe { sdl::SDL_AudioStreamGet(self.raw, buf.as_mut_ptr() as *mut c_void, buf.len() as i32) };
+
+        if read < 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(read as usize);
+    }
+
+    /// Number of converted bytes ready to be read.
+    #[doc(alias = "SDL_AudioStreamAvailable")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn available(&self) -> usize {
+        unsafe { sdl::SDL_AudioStreamAvailable(self.raw).max(0) as usize }
+    }
+
+    /// Converts what is still buffered, e.g. after pushing the last chunk.
+    #[doc(alias = "SDL_AudioStreamFlush")]
+    pub fn flush(&mut self) -> anyhow::Result<()> {
+        unsafe {
+            if sdl::SDL_AudioStreamFlush(self.raw) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+
+    #[doc(alias = "SDL_AudioStreamClear")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn clear(&mut self) {
+        unsafe { sdl::SDL_AudioStreamClear(self.raw) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The lock must be held while the subsystem is alive.
+    fn dummy_audio(driver: &str) -> AudioSubsystem {
+        // overrides SDL_AUDIODRIVER from the environment
+        let priority = sdl::SDL_HintPriority::SDL_HINT_OVERRIDE;
+        assert!(unsafe { sdl::SDL_SetHintWithPriority(sdl::SDL_HINT_AUDIODRIVER.as_ptr() as *const _, driver.as_ptr() as *const _, priority) } == sdl::SDL_bool::SDL_TRUE);
+        let audio = AudioSubsystem::new().unwrap();
+        assert_eq!(audio.current_driver(), driver.trim_end_matches('\0'));
+        return audio;
+    }
+
+    /// Fills the output with a ramp and counts what it was asked for.
+    struct Ramp {
+        calls: u32,
+        samples: usize,
+    }
+
+    impl AudioCallback for Ramp {
+        type Sample = i16;
+
+        fn callback(&mut self, samples: &mut [i16]) {
+            for (i, sample) in samples.iter_mut().enumerate() {
+                *sample = i as i16;
+            }
+            self.calls += 1;
+            self.samples += samples.len();
+        }
+    }
+
+    fn wait_for<F: FnMut() -> bool>(mut done: F) {
+        let start = std::time::Instant::now();
+        while !done() {
+            assert!(start.elapsed() < std::time::Duration::from_secs(5), "timed out");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn callback_device_obtains_the_spec_and_runs_while_resumed() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        let audio = dummy_audio("dummy\0");
+
+        let spec = AudioSpec::new(22050, AudioFormat::s16_sys(), 2, 512);
+        let mut device = audio.open_playback(None, &spec, Ramp { calls: 0, samples: 0 }).unwrap();
+        assert_eq!(*device.spec(), spec);
+        assert_eq!(device.status(), AudioStatus::Paused);
+        assert_eq!(device.lock().calls, 0);
+
+        device.resume();
+        assert_eq!(device.status(), AudioStatus::Playing);
+        wait_for(|| device.lock().calls >= 2);
+
+        device.pause();
+        assert_eq!(device.status(), AudioStatus::Paused);
+        let calls = device.lock().calls;
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert_eq!(device.lock().calls, calls);
+
+        let ramp = device.close_and_get_callback();
+        assert_eq!(ramp.samples, ramp.calls as usize * 512 * 2);
+    }
+
+    #[test]
+    fn open_rejects_a_format_other_than_the_sample_type() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        let audio = dummy_audio("dummy\0");
+
+        let spec = AudioSpec::new(22050, AudioFormat::f32_sys(), 2, 512);
+        assert!(audio.open_playback(None, &spec, Ramp { calls: 0, samples: 0 }).is_err());
+        assert!(audio.open_queue::<i16>(None, &spec).is_err());
+        assert!(audio.open_queue::<f32>(None, &spec).is_ok());
+    }
+
+    #[test]
+    fn queue_holds_the_samples_until_cleared() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        let audio = dummy_audio("dummy\0");
+
+        let queue = audio.open_queue::<i16>(None, &AudioSpec::new(22050, AudioFormat::s16_sys(), 2, 512)).unwrap();
+        assert_eq!(queue.size(), 0);
+        queue.queue_audio(&[0; 1000]).unwrap();
+        queue.queue_audio(&[1; 24]).unwrap();
+        // paused, nothing is played yet
+        assert_eq!(queue.size(), 2048);
+
+        queue.clear();
+        assert_eq!(queue.size(), 0);
+    }
+
+    const S16: [i16; 4] = [0, 16384, -32768, 32767];
+    const F32: [f32; 4] = [0.0, 0.5, -1.0, 32767.0 / 32768.0];
+
+    fn s16_bytes() -> Vec<u8> {
+        S16.iter().flat_map(|s| s.to_ne_bytes()).collect()
+    }
+
+    #[test]
+    fn buffer_checks_the_rate_and_the_frame_size() {
+        let spec = AudioSpec::new(8000, AudioFormat::s16_sys(), 2, 4096);
+        let buffer = AudioBuffer::from_bytes(spec, s16_bytes()).unwrap();
+        assert_eq!(buffer.samples::<i16>(), Some(&S16[..]));
+        assert_eq!(buffer.samples::<f32>(), None);
+        assert_eq!(buffer.frames(), 2);
+
+        assert!(AudioBuffer::from_bytes(AudioSpec { freq: 0, ..spec }, s16_bytes()).is_err());
+        assert!(AudioBuffer::from_bytes(AudioSpec { freq: -8000, ..spec }, s16_bytes()).is_err());
+        assert!(AudioBuffer::from_bytes(spec, vec![0; 6]).is_err());
+        assert!(AudioBuffer::from_samples(spec, &F32).is_err());
+    }
+
+    #[test]
+    fn samples_of_a_misaligned_buffer_are_none() {
+        unsafe {
+            let block = sdl::SDL_malloc(9) as *mut u8;
+            let buffer = AudioBuffer { spec: AudioSpec::new(8000, AudioFormat::s16_sys(), 1, 4096), data: AudioData::Wav { buf: block.add(1), len: 8 } };
+            assert_eq!(buffer.frames(), 4);
+            assert_eq!(buffer.samples::<i16>(), None);
+
+            // `block.add(1)` is not a pointer SDL_FreeWAV could take
+            std::mem::forget(buffer);
+            sdl::SDL_free(block as *mut c_void);
+        }
+    }
+
+    #[test]
+    fn s16_converts_to_exact_f32() {
+        let s16 = AudioBuffer::from_samples(AudioSpec::new(8000, AudioFormat::s16_sys(), 1, 4096), &S16).unwrap();
+        let f32 = s16.convert(&AudioSpec::new(8000, AudioFormat::f32_sys(), 1, 1024)).unwrap();
+        assert_eq!(*f32.spec(), AudioSpec::new(8000, AudioFormat::f32_sys(), 1, 4096));
+        assert_eq!(f32.samples::<f32>(), Some(&F32[..]));
+
+        let cvt = AudioCVT::new(AudioFormat::s16_sys(), 1, 8000, AudioFormat::f32_sys(), 1, 8000).unwrap();
+        assert!(cvt.is_needed());
+        let bytes = cvt.convert(s16_bytes()).unwrap();
+        let samples: Vec<f32> = bytes.chunks_exact(4).map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]])).collect();
+        assert_eq!(samples, F32);
+
+        let same = AudioCVT::new(AudioFormat::s16_sys(), 1, 8000, AudioFormat::s16_sys(), 1, 8000).unwrap();
+        assert!(!same.is_needed());
+        assert_eq!(same.convert(s16_bytes()).unwrap(), s16_bytes());
+    }
+
+    #[test]
+    fn stream_converts_chunks() {
+        let mut stream = AudioStream::new(AudioFormat::s16_sys(), 1, 8000, AudioFormat::f32_sys(), 1, 8000).unwrap();
+        let bytes = s16_bytes();
+        stream.put(&bytes[..4]).unwrap();
+        stream.put(&bytes[4..]).unwrap();
+        stream.flush().unwrap();
+        assert_eq!(stream.available(), 16);
+
+        let mut out = [0u8; 32];
+        assert_eq!(stream.get(&mut out).unwrap(), 16);
+        let samples: Vec<f32> = out[..16].chunks_exact(4).map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]])).collect();
+        assert_eq!(samples, F32);
+        assert_eq!(stream.available(), 0);
+
+        stream.put(&bytes).unwrap();
+        stream.clear();
+        assert_eq!(stream.available(), 0);
+    }
+
+    /// Records what a capture device hands over, the dummy driver captures silence.
+    struct Recorder {
+        samples: usize,
+        silent: bool,
+    }
+
+    impl AudioCallback for Recorder {
+        type Sample = u8;
+
+        fn callback(&mut self, samples: &mut [u8]) {
+            self.samples += samples.len();
+            self.silent &= samples.iter().all(|&s| s == u8::SILENCE);
+        }
+    }
+
+    #[test]
+    fn capture_devices_are_listed() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        let audio = dummy_audio("dummy\0");
+
+        assert_eq!(audio.num_capture_devices(), Some(1));
+        assert_eq!(audio.capture_device_name(0).unwrap(), "System audio capture device");
+        assert!(audio.capture_device_name(1).is_err());
+        assert_eq!(audio.num_playback_devices(), Some(1));
+        assert_eq!(audio.playback_device_name(0).unwrap(), "System audio output device");
+    }
+
+    #[test]
+    fn capture_callback_gets_the_recorded_samples() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        let audio = dummy_audio("dummy\0");
+
+        let name = audio.capture_device_name(0).unwrap();
+        let spec = AudioSpec::new(8000, AudioFormat::U8, 1, 256);
+        let mut device = audio.open_capture(Some(&name), &spec, Recorder { samples: 0, silent: true }).unwrap();
+        assert_eq!(*device.spec(), spec);
+
+        device.resume();
+        wait_for(|| device.lock().samples >= 512);
+        let recorder = device.close_and_get_callback();
+        assert!(recorder.samples.is_multiple_of(256));
+        assert!(recorder.silent);
+    }
+
+    #[test]
+    fn capture_queue_dequeues_the_recorded_samples() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        let audio = dummy_audio("dummy\0");
+
+        let queue = audio.open_capture_queue::<u8>(None, &AudioSpec::new(8000, AudioFormat::U8, 1, 256)).unwrap();
+        let mut samples = [0u8; 300];
+        assert_eq!(queue.dequeue_audio(&mut samples), 0);
+
+        queue.resume();
+        wait_for(|| queue.size() >= 512);
+        assert_eq!(queue.dequeue_audio(&mut samples), 300);
+        assert!(samples.iter().all(|&s| s == u8::SILENCE));
+    }
+}
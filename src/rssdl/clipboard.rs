@@ -0,0 +1,62 @@
+use std::ffi::CString;
+
+use anyhow;
+use sdl2_sys as sdl;
+
+/// Copies an SDL allocated string and frees it.
+unsafe fn take_sdl_string(raw: *mut std::ffi::c_char) -> anyhow::Result<String> {
+    if raw.is_null() {
+        return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+    }
+
+    let text = std::ffi::CStr::from_ptr(raw).to_string_lossy().into_owned();
+    sdl::SDL_free(raw as *mut _);
+    return Ok(text);
+}
+
+#[doc(alias = "SDL_SetClipboardText")]
+pub fn set_clipboard_text(text: &str) -> anyhow::Result<()> {
+    let text = CString::new(text)?;
+    unsafe {
+        if sdl::SDL_SetClipboardText(text.as_ptr()) != 0 {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+        }
+    }
+
+    return Ok(());
+}
+
+/// The clipboard text, empty if the clipboard holds no text.
+#[doc(alias = "SDL_GetClipboardText")]
+pub fn clipboard_text() -> anyhow::Result<String> {
+    unsafe { take_sdl_string(sdl::SDL_GetClipboardText()) }
+}
+
+#[doc(alias = "SDL_HasClipboardText")]
+pub fn has_clipboard_text() -> bool {
+    unsafe { sdl::SDL_HasClipboardText() == sdl::SDL_bool::SDL_TRUE }
+}
+
+/// Sets the primary selection, the text pasted with a middle click on X11 and Wayland.
+#[doc(alias = "SDL_SetPrimarySelectionText")]
+pub fn set_primary_selection_text(text: &str) -> anyhow::Result<()> {
+    let text = CString::new(text)?;
+    unsafe {
+        if sdl::SDL_SetPrimarySelectionText(text.as_ptr()) != 0 {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+        }
+    }
+
+    return Ok(());
+}
+
+/// The primary selection text, empty if there is none.
+#[doc(alias = "SDL_GetPrimarySelectionText")]
+pub fn primary_selection_text() -> anyhow::Result<String> {
+    unsafe { take_sdl_string(sdl::SDL_GetPrimarySelectionText()) }
+}
+
+#[doc(alias = "SDL_HasPrimarySelectionText")]
+pub fn has_primary_selection_text() -> bool {
+    unsafe { sdl::SDL_HasPrimarySelectionText() == sdl::SDL_bool::SDL_TRUE }
+}
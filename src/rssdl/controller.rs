@@ -0,0 +1,405 @@
+use std::{ffi::CString, marker::PhantomData};
+
+use anyhow;
+use sdl2_sys as sdl;
+
+use crate::{core::Color, rwops::RWops};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Button {
+    A,
+    B,
+    X,
+    Y,
+    Back,
+    Guide,
+    Start,
+    LeftStick,
+    RightStick,
+    LeftShoulder,
+    RightShoulder,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    Misc1,
+    Paddle1,
+    Paddle2,
+    Paddle3,
+    Paddle4,
+    Touchpad,
+}
+
+impl Button {
+    pub fn from_ll(button: u8) -> Option<Button> {
+        let button = match button as i32 {
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_A as i32 => Button::A,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_B as i32 => Button::B,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_X as i32 => Button::X,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_Y as i32 => Button::Y,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_BACK as i32 => Button::Back,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_GUIDE as i32 => Button::Guide,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_START as i32 => Button::Start,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_LEFTSTICK as i32 => Button::LeftStick,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_RIGHTSTICK as i32 => Button::RightStick,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_LEFTSHOULDER as i32 => Button::LeftShoulder,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_RIGHTSHOULDER as i32 => Button::RightShoulder,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_DPAD_UP as i32 => Button::DPadUp,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_DPAD_DOWN as i32 => Button::DPadDown,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_DPAD_LEFT as i32 => Button::DPadLeft,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_DPAD_RIGHT as i32 => Button::DPadRight,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_MISC1 as i32 => Button::Misc1,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_PADDLE1 as i32 => Button::Paddle1,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_PADDLE2 as i32 => Button::Paddle2,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_PADDLE3 as i32 => Button::Paddle3,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_PADDLE4 as i32 => Button::Paddle4,
+            x if x == sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_TOUCHPAD as i32 => Button::Touchpad,
+            _ => return None,
+        };
+
+        return Some(button);
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn to_ll(self) -> sdl::SDL_GameControllerButton {
+        match self {
+            Button::A => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_A,
+            Button::B => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_B,
+            Button::X => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_X,
+            Button::Y => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_Y,
+            Button::Back => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_BACK,
+            Button::Guide => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_GUIDE,
+            Button::Start => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_START,
+            Button::LeftStick => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_LEFTSTICK,
+            Button::RightStick => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_RIGHTSTICK,
+            Button::LeftShoulder => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_LEFTSHOULDER,
+            Button::RightShoulder => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_RIGHTSHOULDER,
+            Button::DPadUp => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_DPAD_UP,
+            Button::DPadDown => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_DPAD_DOWN,
+            Button::DPadLeft => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_DPAD_LEFT,
+            Button::DPadRight => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_DPAD_RIGHT,
+            Button::Misc1 => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_MISC1,
+            Button::Paddle1 => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_PADDLE1,
+            Button::Paddle2 => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_PADDLE2,
+            Button::Paddle3 => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_PADDLE3,
+            Button::Paddle4 => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_PADDLE4,
+            Button::Touchpad => sdl::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_TOUCHPAD,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Axis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    TriggerLeft,
+    TriggerRight,
+}
+
+impl Axis {
+    pub fn from_ll(axis: u8) -> Option<Axis> {
+        let axis = match axis as i32 {
+            x if x == sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_LEFTX as i32 => Axis::LeftX,
+            x if x == sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_LEFTY as i32 => Axis::LeftY,
+            x if x == sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_RIGHTX as i32 => Axis::RightX,
+            x if x == sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_RIGHTY as i32 => Axis::RightY,
+            x if x == sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_TRIGGERLEFT as i32 => Axis::TriggerLeft,
+            x if x == sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_TRIGGERRIGHT as i32 => Axis::TriggerRight,
+            _ => return None,
+        };
+
+        return Some(axis);
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn to_ll(self) -> sdl::SDL_GameControllerAxis {
+        match self {
+            Axis::LeftX => sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_LEFTX,
+            Axis::LeftY => sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_LEFTY,
+            Axis::RightX => sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_RIGHTX,
+            Axis::RightY => sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_RIGHTY,
+            Axis::TriggerLeft => sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_TRIGGERLEFT,
+            Axis::TriggerRight => sdl::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_TRIGGERRIGHT,
+        }
+    }
+}
+
+/// Initializes the joystick and game controller subsystems.
+///
+/// Keep it alive while controllers are in use, `SDL_CONTROLLERDEVICEADDED` is sent
+/// for every controller already connected when it is created.
+pub struct GameControllerSubsystem;
+
+impl Drop for GameControllerSubsystem {
+    fn drop(&mut self) {
+        unsafe {
+            sdl::SDL_QuitSubSystem(sdl::SDL_INIT_GAMECONTROLLER);
+        }
+    }
+}
+
+impl GameControllerSubsystem {
+    pub fn new() -> anyhow::Result<GameControllerSubsystem> {
+        // SDL_INIT_GAMECONTROLLER implies SDL_INIT_JOYSTICK
+        let res = unsafe { sdl::SDL_InitSubSystem(sdl::SDL_INIT_GAMECONTROLLER) };
+
+        if res != 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(GameControllerSubsystem {});
+    }
+
+    /// Number of attached joysticks, controllers included.
+    #[doc(alias = "SDL_NumJoysticks")]
+    pub fn num_joysticks(&self) -> anyhow::Result<u32> {
+        let count = unsafe { sdl::SDL_NumJoysticks() };
+
+        if count < 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(count as u32);
+    }
+
+    /// Whether the joystick at `device_index` has a controller mapping.
+    #[doc(alias = "SDL_IsGameController")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_game_controller(&self, device_index: u32) -> bool {
+        unsafe { sdl::SDL_IsGameController(device_index as i32) == sdl::SDL_bool::SDL_TRUE }
+    }
+
+    #[doc(alias = "SDL_GameControllerNameForIndex")]
+    pub fn name_for_index(&self, device_index: u32) -> anyhow::Result<String> {
+        let name = unsafe { sdl::SDL_GameControllerNameForIndex(device_index as i32) };
+
+        if name.is_null() {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(unsafe { std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned() });
+    }
+
+    /// Opens the controller at `device_index`, the `which` of `Event::ControllerDeviceAdded`.
+    #[doc(alias = "SDL_GameControllerOpen")]
+    pub fn open(&self, device_index: u32) -> anyhow::Result<GameController<'_>> {
+        let raw = unsafe { sdl::SDL_GameControllerOpen(device_index as i32) };
+
+        if raw.is_null() {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(GameController { raw, _marker: PhantomData });
+    }
+
+    /// Adds a single mapping line in the `gamecontrollerdb.txt` format.
+    ///
+    /// Returns true if a new mapping was added, false if an existing one was updated.
+    #[doc(alias = "SDL_GameControllerAddMapping")]
+    pub fn add_mapping(&self, mapping: &str) -> anyhow::Result<bool> {
+        let mapping = CString::new(mapping)?;
+        let res = unsafe { sdl::SDL_GameControllerAddMapping(mapping.as_ptr()) };
+
+        if res < 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(res == 1);
+    }
+
+    /// Loads the mappings for the current platform from a `gamecontrollerdb.txt`,
+    /// e.g. `RWops::from_file("gamecontrollerdb.txt\0", "rb\0")`. Returns the number of added mappings.
+    #[doc(alias = "SDL_GameControllerAddMappingsFromRW")]
+    pub fn load_mappings(&self, rwops: &mut RWops) -> anyhow::Result<u32> {
+        let res = unsafe { sdl::SDL_GameControllerAddMappingsFromRW(rwops.raw, 0) };
+
+        if res < 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(res as u32);
+    }
+}
+
+/// An open controller, closed on drop. Borrows the subsystem so it can't outlive it.
+pub struct GameController<'a> {
+    raw: *mut sdl::SDL_GameController,
+    _marker: PhantomData<&'a GameControllerSubsystem>,
+}
+
+impl<'a> Drop for GameController<'a> {
+    #[doc(alias = "SDL_GameControllerClose")]
+    fn drop(&mut self) {
+        unsafe {
+            sdl::SDL_GameControllerClose(self.raw);
+        }
+    }
+}
+
+impl<'a> GameController<'a> {
+    #[doc(alias = "SDL_GameControllerName")]
+    pub fn name(&self) -> String {
+        unsafe {
+            let name = sdl::SDL_GameControllerName(self.raw);
+            if name.is_null() {
+                return String::new();
+            }
+            return std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned();
+        }
+    }
+
+    /// The mapping string of this controller, `None` if it has none.
+    #[doc(alias = "SDL_GameControllerMapping")]
+    pub fn mapping(&self) -> Option<String> {
+        unsafe {
+            let mapping = sdl::SDL_GameControllerMapping(self.raw);
+            if mapping.is_null() {
+                return None;
+            }
+            let text = std::ffi::CStr::from_ptr(mapping).to_string_lossy().into_owned();
+            sdl::SDL_free(mapping as *mut _);
+            return Some(text);
+        }
+    }
+
+    /// The id used as `which` by every controller event except `ControllerDeviceAdded`.
+    #[doc(alias = "SDL_JoystickInstanceID")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn instance_id(&self) -> i32 {
+        unsafe { sdl::SDL_JoystickInstanceID(sdl::SDL_GameControllerGetJoystick(self.raw)) }
+    }
+
+    /// False once the controller was unplugged.
+    #[doc(alias = "SDL_GameControllerGetAttached")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn attached(&self) -> bool {
+        unsafe { sdl::SDL_GameControllerGetAttached(self.raw) == sdl::SDL_bool::SDL_TRUE }
+    }
+
+    #[doc(alias = "SDL_GameControllerGetButton")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn button(&self, button: Button) -> bool {
+        unsafe { sdl::SDL_GameControllerGetButton(self.raw, button.to_ll()) != 0 }
+    }
+
+    /// Sticks range from -32768 to 32767, triggers from 0 to 32767.
+    #[doc(alias = "SDL_GameControllerGetAxis")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn axis(&self, axis: Axis) -> i16 {
+        unsafe { sdl::SDL_GameControllerGetAxis(self.raw, axis.to_ll()) }
+    }
+
+    #[doc(alias = "SDL_GameControllerHasRumble")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn has_rumble(&self) -> bool {
+        unsafe { sdl::SDL_GameControllerHasRumble(self.raw) == sdl::SDL_bool::SDL_TRUE }
+    }
+
+    /// Starts a rumble effect, a new call replaces the previous one and a `duration_ms` of 0 stops it.
+    #[doc(alias = "SDL_GameControllerRumble")]
+    pub fn set_rumble(&mut self, low_frequency: u16, high_frequency: u16, duration_ms: u32) -> anyhow::Result<()> {
+        unsafe {
+            if sdl::SDL_GameControllerRumble(self.raw, low_frequency, high_frequency, duration_ms) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Rumble in the triggers, e.g. on Xbox One controllers.
+    #[doc(alias = "SDL_GameControllerRumbleTriggers")]
+    pub fn set_rumble_triggers(&mut self, left: u16, right: u16, duration_ms: u32) -> anyhow::Result<()> {
+        unsafe {
+            if sdl::SDL_GameControllerRumbleTriggers(self.raw, left, right, duration_ms) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+
+    #[doc(alias = "SDL_GameControllerHasLED")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn has_led(&self) -> bool {
+        unsafe { sdl::SDL_GameControllerHasLED(self.raw) == sdl::SDL_bool::SDL_TRUE }
+    }
+
+    /// Sets the light bar color, the alpha is ignored.
+    #[doc(alias = "SDL_GameControllerSetLED")]
+    pub fn set_led(&mut self, color: impl Into<Color>) -> anyhow::Result<()> {
+        let color: Color = color.into();
+        unsafe {
+            if sdl::SDL_GameControllerSetLED(self.raw, color.r, color.g, color.b) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        events::{self, Event},
+        joystick::{JoystickSubsystem, JoystickType},
+    };
+
+    #[test]
+    fn virtual_controller_buttons_axes_and_events() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        let controllers = GameControllerSubsystem::new().unwrap();
+        let joysticks = JoystickSubsystem::new().unwrap();
+        while events::poll_event().is_some() {}
+
+        let index = joysticks.attach_virtual(JoystickType::GameController, 6, 15, 0).unwrap();
+        assert!(controllers.is_game_controller(index));
+        let added: Vec<Event> = std::iter::from_fn(events::poll_event).filter(|e| matches!(e, Event::ControllerDeviceAdded { .. })).collect();
+        assert!(matches!(added[..], [Event::ControllerDeviceAdded { which, .. }] if which == index));
+
+        let controller = controllers.open(index).unwrap();
+        let mut joystick = joysticks.open(index).unwrap();
+        assert!(controller.mapping().is_some());
+
+        // the virtual buttons and axes are in `Button` and `Axis` order
+        joystick.set_virtual_button(Button::A.to_ll() as u32, true).unwrap();
+        joystick.set_virtual_button(Button::DPadLeft.to_ll() as u32, true).unwrap();
+        joystick.set_virtual_axis(Axis::RightY.to_ll() as u32, -20000).unwrap();
+        joysticks.update();
+
+        assert!(controller.button(Button::A));
+        assert!(controller.button(Button::DPadLeft));
+        assert!(!controller.button(Button::B));
+        assert_eq!(controller.axis(Axis::RightY), -20000);
+        assert_eq!(controller.axis(Axis::LeftX), 0);
+
+        let id = controller.instance_id();
+        let pressed: Vec<Button> = std::iter::from_fn(events::poll_event)
+            .filter_map(|e| match e {
+                Event::ControllerButtonDown { which, button, .. } if which == id => Some(button),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(pressed, [Button::A, Button::DPadLeft]);
+
+        drop(joystick);
+        drop(controller);
+        joysticks.detach_virtual(index).unwrap();
+    }
+
+    #[test]
+    fn add_mapping_tells_new_from_updated() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        let controllers = GameControllerSubsystem::new().unwrap();
+
+        let mapping = "03000000dead0000beef000000000000,Test Pad,a:b0,b:b1,leftx:a0,lefty:a1,";
+        assert!(controllers.add_mapping(mapping).unwrap());
+        assert!(!controllers.add_mapping(mapping).unwrap());
+        assert!(controllers.add_mapping("not a mapping").is_err());
+        assert!(controllers.add_mapping("with\0nul").is_err());
+    }
+}
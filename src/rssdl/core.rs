@@ -6,6 +6,7 @@ pub struct FpsCap {
     frame_delay: u32,
     pub dt: f32,
     last_time: u64,
+    frame: u64,
 }
 static mut PERFORMANCE_FREQUENCY: f32 = 0.0;
 
@@ -16,7 +17,7 @@ impl FpsCap {
             PERFORMANCE_FREQUENCY = sdl::SDL_GetPerformanceFrequency() as f32;
         }
 
-        Self { frame_delay: 1000 / fps_cap, dt: 0.0, last_time: unsafe { sdl::SDL_GetPerformanceCounter() } }
+        Self { frame_delay: 1000 / fps_cap, dt: 0.0, last_time: unsafe { sdl::SDL_GetPerformanceCounter() }, frame: 0 }
     }
     #[cfg_attr(not(debug_assertions), inline(always))]
     pub fn start(&mut self) {
@@ -25,6 +26,13 @@ impl FpsCap {
         self.dt = (elapsed as f32) / (unsafe { PERFORMANCE_FREQUENCY });
         // println!("FPS: {} | dt {}", 1.0 / self.dt, self.dt);
         self.last_time = now2;
+        self.frame += 1;
+    }
+
+    /// Number of times `start` was called, i.e. the 1-based index of the current frame.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn frame(&self) -> u64 {
+        self.frame
     }
     #[cfg_attr(not(debug_assertions), inline(always))]
     pub fn end(&self) {
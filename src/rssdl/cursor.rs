@@ -0,0 +1,113 @@
+use std::path::Path;
+
+use anyhow;
+use sdl2_sys as sdl;
+
+use crate::surface::Surface;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SystemCursor {
+    Arrow,
+    IBeam,
+    Wait,
+    Crosshair,
+    WaitArrow,
+    SizeNWSE,
+    SizeNESW,
+    SizeWE,
+    SizeNS,
+    SizeAll,
+    No,
+    Hand,
+}
+
+impl SystemCursor {
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn to_ll(self) -> sdl::SDL_SystemCursor {
+        match self {
+            SystemCursor::Arrow => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_ARROW,
+            SystemCursor::IBeam => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_IBEAM,
+            SystemCursor::Wait => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_WAIT,
+            SystemCursor::Crosshair => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_CROSSHAIR,
+            SystemCursor::WaitArrow => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_WAITARROW,
+            SystemCursor::SizeNWSE => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_SIZENWSE,
+            SystemCursor::SizeNESW => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_SIZENESW,
+            SystemCursor::SizeWE => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_SIZEWE,
+            SystemCursor::SizeNS => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_SIZENS,
+            SystemCursor::SizeAll => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_SIZEALL,
+            SystemCursor::No => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_NO,
+            SystemCursor::Hand => sdl::SDL_SystemCursor::SDL_SYSTEM_CURSOR_HAND,
+        }
+    }
+}
+
+/// A mouse cursor image. Dropping the active cursor restores the default one.
+pub struct Cursor {
+    raw: *mut sdl::SDL_Cursor,
+}
+
+impl Drop for Cursor {
+    #[doc(alias = "SDL_FreeCursor")]
+    fn drop(&mut self) {
+        unsafe {
+            sdl::SDL_FreeCursor(self.raw);
+        }
+    }
+}
+
+impl Cursor {
+    /// Creates a cursor from the pixels of `surface`, `(hot_x, hot_y)` is the clicking point.
+    #[doc(alias = "SDL_CreateColorCursor")]
+    pub fn from_surface(surface: &Surface, hot_x: i32, hot_y: i32) -> anyhow::Result<Cursor> {
+        let raw = unsafe { sdl::SDL_CreateColorCursor(surface.raw, hot_x, hot_y) };
+
+        if raw.is_null() {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        } else {
+            return Ok(Cursor { raw });
+        }
+    }
+
+    /// Loads the cursor image with SDL2_image, e.g. `assets/cursor.png\0`.
+    pub fn from_file<P: AsRef<Path>>(path: P, hot_x: i32, hot_y: i32) -> anyhow::Result<Cursor> {
+        let surface = Surface::load_image(path)?;
+        return Cursor::from_surface(&surface, hot_x, hot_y);
+    }
+
+    #[doc(alias = "SDL_CreateSystemCursor")]
+    pub fn from_system(cursor: SystemCursor) -> anyhow::Result<Cursor> {
+        let raw = unsafe { sdl::SDL_CreateSystemCursor(cursor.to_ll()) };
+
+        if raw.is_null() {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        } else {
+            return Ok(Cursor { raw });
+        }
+    }
+
+    /// Makes this the active cursor.
+    #[doc(alias = "SDL_SetCursor")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn set(&self) {
+        unsafe {
+            sdl::SDL_SetCursor(self.raw);
+        }
+    }
+}
+
+#[doc(alias = "SDL_ShowCursor")]
+pub fn show_cursor(show: bool) -> anyhow::Result<()> {
+    let toggle = if show { sdl::SDL_ENABLE } else { sdl::SDL_DISABLE };
+    unsafe {
+        if sdl::SDL_ShowCursor(toggle as i32) < 0 {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+        }
+    }
+
+    return Ok(());
+}
+
+#[doc(alias = "SDL_ShowCursor")]
+pub fn is_cursor_showing() -> bool {
+    unsafe { sdl::SDL_ShowCursor(sdl::SDL_QUERY) == sdl::SDL_ENABLE as i32 }
+}
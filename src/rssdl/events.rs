@@ -1,10 +1,31 @@
+use std::{
+    any::{Any, TypeId},
+    ffi::c_void,
+    sync::{Arc, Mutex},
+};
+
+use anyhow;
 use sdl2_sys as sdl;
 
-pub type Event = sdl::SDL_Event;
+pub use crate::keyboard::{Keycode, Mod, Scancode};
+use crate::{controller, touch};
+
+pub type RawEvent = sdl::SDL_Event;
 pub type EventType = sdl::SDL_EventType;
-pub type Scancode = sdl::SDL_Scancode;
+/// Like `try_type`, but ids without an `SDL_EventType` variant come back as `SDL_USEREVENT`
+/// when they are in the user range and as `SDL_FIRSTEVENT` otherwise.
 #[cfg_attr(not(debug_assertions), inline(always))]
-pub fn type_(e: Event) -> EventType {
+pub fn type_(e: RawEvent) -> EventType {
+    match try_type(e) {
+        Some(t) => t,
+        None if unsafe { e.type_ } >= sdl::SDL_EventType::SDL_USEREVENT as u32 => sdl::SDL_EventType::SDL_USEREVENT,
+        None => sdl::SDL_EventType::SDL_FIRSTEVENT,
+    }
+}
+
+/// Like `type_`, but returns `None` for ids without an `SDL_EventType` variant,
+/// e.g. types returned by `SDL_RegisterEvents` or added by a newer SDL.
+pub fn try_type(e: RawEvent) -> Option<EventType> {
     const SDL_FIRSTEVENTU32: u32 = sdl::SDL_EventType::SDL_FIRSTEVENT as u32;
     const SDL_QUITU32: u32 = sdl::SDL_EventType::SDL_QUIT as u32;
     const SDL_APP_TERMINATINGU32: u32 = sdl::SDL_EventType::SDL_APP_TERMINATING as u32;
@@ -21,6 +42,7 @@ pub fn type_(e: Event) -> EventType {
     const SDL_KEYUPU32: u32 = sdl::SDL_EventType::SDL_KEYUP as u32;
     const SDL_TEXTEDITINGU32: u32 = sdl::SDL_EventType::SDL_TEXTEDITING as u32;
     const SDL_TEXTINPUTU32: u32 = sdl::SDL_EventType::SDL_TEXTINPUT as u32;
+    const SDL_TEXTEDITING_EXTU32: u32 = sdl::SDL_EventType::SDL_TEXTEDITING_EXT as u32;
     const SDL_KEYMAPCHANGEDU32: u32 = sdl::SDL_EventType::SDL_KEYMAPCHANGED as u32;
     const SDL_MOUSEMOTIONU32: u32 = sdl::SDL_EventType::SDL_MOUSEMOTION as u32;
     const SDL_MOUSEBUTTONDOWNU32: u32 = sdl::SDL_EventType::SDL_MOUSEBUTTONDOWN as u32;
@@ -63,7 +85,7 @@ pub fn type_(e: Event) -> EventType {
     const SDL_USEREVENTU32: u32 = sdl::SDL_EventType::SDL_USEREVENT as u32;
     const SDL_LASTEVENTU32: u32 = sdl::SDL_EventType::SDL_LASTEVENT as u32;
 
-    match unsafe { e.type_ } {
+    let t = match unsafe { e.type_ } {
         SDL_FIRSTEVENTU32 => sdl::SDL_EventType::SDL_FIRSTEVENT,
         SDL_QUITU32 => sdl::SDL_EventType::SDL_QUIT,
         SDL_APP_TERMINATINGU32 => sdl::SDL_EventType::SDL_APP_TERMINATING,
@@ -80,6 +102,7 @@ pub fn type_(e: Event) -> EventType {
         SDL_KEYUPU32 => sdl::SDL_EventType::SDL_KEYUP,
         SDL_TEXTEDITINGU32 => sdl::SDL_EventType::SDL_TEXTEDITING,
         SDL_TEXTINPUTU32 => sdl::SDL_EventType::SDL_TEXTINPUT,
+        SDL_TEXTEDITING_EXTU32 => sdl::SDL_EventType::SDL_TEXTEDITING_EXT,
         SDL_KEYMAPCHANGEDU32 => sdl::SDL_EventType::SDL_KEYMAPCHANGED,
         SDL_MOUSEMOTIONU32 => sdl::SDL_EventType::SDL_MOUSEMOTION,
         SDL_MOUSEBUTTONDOWNU32 => sdl::SDL_EventType::SDL_MOUSEBUTTONDOWN,
@@ -121,11 +144,532 @@ pub fn type_(e: Event) -> EventType {
         SDL_POLLSENTINELU32 => sdl::SDL_EventType::SDL_POLLSENTINEL,
         SDL_USEREVENTU32 => sdl::SDL_EventType::SDL_USEREVENT,
         SDL_LASTEVENTU32 => sdl::SDL_EventType::SDL_LASTEVENT,
-        _ => unreachable!(),
+        _ => return None,
+    };
+
+    return Some(t);
+}
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum WindowEvent {
+    None,
+    Shown,
+    Hidden,
+    Exposed,
+    Moved(i32, i32),
+    Resized(i32, i32),
+    SizeChanged(i32, i32),
+    Minimized,
+    Maximized,
+    Restored,
+    Enter,
+    Leave,
+    FocusGained,
+    FocusLost,
+    Close,
+    TakeFocus,
+    HitTest,
+    ICCProfChanged,
+    DisplayChanged(i32),
+}
+
+impl WindowEvent {
+    pub fn from_ll(id: u8, data1: i32, data2: i32) -> WindowEvent {
+        const NONE: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_NONE as u8;
+        const SHOWN: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_SHOWN as u8;
+        const HIDDEN: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_HIDDEN as u8;
+        const EXPOSED: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_EXPOSED as u8;
+        const MOVED: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_MOVED as u8;
+        const RESIZED: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_RESIZED as u8;
+        const SIZE_CHANGED: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_SIZE_CHANGED as u8;
+        const MINIMIZED: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_MINIMIZED as u8;
+        const MAXIMIZED: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_MAXIMIZED as u8;
+        const RESTORED: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_RESTORED as u8;
+        const ENTER: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_ENTER as u8;
+        const LEAVE: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_LEAVE as u8;
+        const FOCUS_GAINED: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_FOCUS_GAINED as u8;
+        const FOCUS_LOST: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_FOCUS_LOST as u8;
+        const CLOSE: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_CLOSE as u8;
+        const TAKE_FOCUS: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_TAKE_FOCUS as u8;
+        const HIT_TEST: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_HIT_TEST as u8;
+        const ICCPROF_CHANGED: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_ICCPROF_CHANGED as u8;
+        const DISPLAY_CHANGED: u8 = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_DISPLAY_CHANGED as u8;
+
+        match id {
+            NONE => WindowEvent::None,
+            SHOWN => WindowEvent::Shown,
+            HIDDEN => WindowEvent::Hidden,
+            EXPOSED => WindowEvent::Exposed,
+            MOVED => WindowEvent::Moved(data1, data2),
+            RESIZED => WindowEvent::Resized(data1, data2),
+            SIZE_CHANGED => WindowEvent::SizeChanged(data1, data2),
+            MINIMIZED => WindowEvent::Minimized,
+            MAXIMIZED => WindowEvent::Maximized,
+            RESTORED => WindowEvent::Restored,
+            ENTER => WindowEvent::Enter,
+            LEAVE => WindowEvent::Leave,
+            FOCUS_GAINED => WindowEvent::FocusGained,
+            FOCUS_LOST => WindowEvent::FocusLost,
+            CLOSE => WindowEvent::Close,
+            TAKE_FOCUS => WindowEvent::TakeFocus,
+            HIT_TEST => WindowEvent::HitTest,
+            ICCPROF_CHANGED => WindowEvent::ICCProfChanged,
+            DISPLAY_CHANGED => WindowEvent::DisplayChanged(data1),
+            _ => WindowEvent::None,
+        }
+    }
+}
+
+/// A safe, owned copy of an `SDL_Event`.
+///
+/// Event types without a dedicated variant yet are reported as `Unknown`
+/// with their raw type id, so nothing coming out of the queue is lost.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Event {
+    Quit { timestamp: u32 },
+    AppTerminating { timestamp: u32 },
+    AppLowMemory { timestamp: u32 },
+    AppWillEnterBackground { timestamp: u32 },
+    AppDidEnterBackground { timestamp: u32 },
+    AppWillEnterForeground { timestamp: u32 },
+    AppDidEnterForeground { timestamp: u32 },
+    LocaleChanged { timestamp: u32 },
+
+    Display { timestamp: u32, display_index: u32, display_event: u8, data1: i32 },
+    Window { timestamp: u32, window_id: u32, win_event: WindowEvent },
+
+    KeyDown { timestamp: u32, window_id: u32, scancode: Scancode, keycode: Keycode, keymod: Mod, repeat: bool },
+    KeyUp { timestamp: u32, window_id: u32, scancode: Scancode, keycode: Keycode, keymod: Mod, repeat: bool },
+    TextEditing { timestamp: u32, window_id: u32, text: String, start: i32, length: i32 },
+    TextInput { timestamp: u32, window_id: u32, text: String },
+    KeymapChanged { timestamp: u32 },
+
+    MouseMotion { timestamp: u32, window_id: u32, which: u32, mousestate: MouseState, x: i32, y: i32, xrel: i32, yrel: i32 },
+    MouseButtonDown { timestamp: u32, window_id: u32, which: u32, mouse_btn: MouseButton, clicks: u8, x: i32, y: i32 },
+    MouseButtonUp { timestamp: u32, window_id: u32, which: u32, mouse_btn: MouseButton, clicks: u8, x: i32, y: i32 },
+    MouseWheel { timestamp: u32, window_id: u32, which: u32, x: i32, y: i32, precise_x: f32, precise_y: f32, flipped: bool },
+
+    /// `which` is the device index to pass to `GameControllerSubsystem::open`.
+    ControllerDeviceAdded { timestamp: u32, which: u32 },
+    /// `which` is the instance id of the removed controller.
+    ControllerDeviceRemoved { timestamp: u32, which: i32 },
+    ControllerDeviceRemapped { timestamp: u32, which: i32 },
+    ControllerAxisMotion { timestamp: u32, which: i32, axis: controller::Axis, value: i16 },
+    ControllerButtonDown { timestamp: u32, which: i32, button: controller::Button },
+    ControllerButtonUp { timestamp: u32, which: i32, button: controller::Button },
+
+    /// `x`, `y`, `dx` and `dy` are normalized to the window, `0.0..=1.0` and `-1.0..=1.0`.
+    FingerDown { timestamp: u32, window_id: u32, touch_id: touch::TouchId, finger_id: touch::FingerId, x: f32, y: f32, dx: f32, dy: f32, pressure: f32 },
+    FingerUp { timestamp: u32, window_id: u32, touch_id: touch::TouchId, finger_id: touch::FingerId, x: f32, y: f32, dx: f32, dy: f32, pressure: f32 },
+    FingerMotion { timestamp: u32, window_id: u32, touch_id: touch::TouchId, finger_id: touch::FingerId, x: f32, y: f32, dx: f32, dy: f32, pressure: f32 },
+    /// Pinch and rotate, `x` and `y` are the normalized center of the fingers.
+    MultiGesture { timestamp: u32, touch_id: touch::TouchId, d_theta: f32, d_dist: f32, x: f32, y: f32, num_fingers: u16 },
+    DollarGesture { timestamp: u32, touch_id: touch::TouchId, gesture_id: touch::GestureId, num_fingers: u32, error: f32, x: f32, y: f32 },
+    /// A template was recorded after `touch::record_gesture`.
+    DollarRecord { timestamp: u32, touch_id: touch::TouchId, gesture_id: touch::GestureId },
+
+    /// `which` is the device index to pass to `playback_device_name`.
+    AudioDeviceAdded { timestamp: u32, which: u32, iscapture: bool },
+    /// `which` is the id of the removed open device.
+    AudioDeviceRemoved { timestamp: u32, which: u32, iscapture: bool },
+
+    ClipboardUpdate { timestamp: u32 },
+
+    DropFile { timestamp: u32, window_id: u32, filename: String },
+    DropText { timestamp: u32, window_id: u32, text: String },
+    DropBegin { timestamp: u32, window_id: u32 },
+    DropComplete { timestamp: u32, window_id: u32 },
+
+    RenderTargetsReset { timestamp: u32 },
+    RenderDeviceReset { timestamp: u32 },
+
+    /// An event pushed with `SDL_PushEvent` by other code. The pointers are kept as addresses,
+    /// so `Event` stays `Send` and `Sync`; they are only valid as long as their owner says.
+    User { timestamp: u32, window_id: u32, type_: u32, code: i32, data1: usize, data2: usize },
+    /// A payload pushed with `push_custom_event`.
+    Custom { timestamp: u32, window_id: u32, type_: u32, payload: CustomPayload },
+
+    Unknown { timestamp: u32, type_: u32 },
+}
+
+unsafe fn c_str_to_string(ptr: *const std::ffi::c_char) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    return std::ffi::CStr::from_ptr(ptr).to_string_lossy().into_owned();
+}
+
+impl Event {
+    /// Converts a raw `SDL_Event` into an `Event`.
+    ///
+    /// The raw event is only borrowed: strings owned by SDL (e.g. the file name of
+    /// `SDL_DROPFILE`) are copied and *not* freed here.
+    pub fn from_ll(raw: &RawEvent) -> Event {
+        unsafe {
+            let timestamp = raw.common.timestamp;
+
+            if raw.type_ >= sdl::SDL_EventType::SDL_USEREVENT as u32 && raw.type_ < sdl::SDL_EventType::SDL_LASTEVENT as u32 {
+                let e = raw.user;
+                if is_custom_event_id(e.type_) && !e.data1.is_null() {
+                    let payload = &*(e.data1 as *const Arc<dyn Any + Send + Sync>);
+                    return Event::Custom { timestamp, window_id: e.windowID, type_: e.type_, payload: CustomPayload(payload.clone()) };
+                }
+                return Event::User { timestamp, window_id: e.windowID, type_: e.type_, code: e.code, data1: e.data1 as usize, data2: e.data2 as usize };
+            }
+
+            let event_type = match try_type(*raw) {
+                Some(t) => t,
+                None => return Event::Unknown { timestamp, type_: raw.type_ },
+            };
+
+            match event_type {
+                EventType::SDL_QUIT => Event::Quit { timestamp },
+                EventType::SDL_APP_TERMINATING => Event::AppTerminating { timestamp },
+                EventType::SDL_APP_LOWMEMORY => Event::AppLowMemory { timestamp },
+                EventType::SDL_APP_WILLENTERBACKGROUND => Event::AppWillEnterBackground { timestamp },
+                EventType::SDL_APP_DIDENTERBACKGROUND => Event::AppDidEnterBackground { timestamp },
+                EventType::SDL_APP_WILLENTERFOREGROUND => Event::AppWillEnterForeground { timestamp },
+                EventType::SDL_APP_DIDENTERFOREGROUND => Event::AppDidEnterForeground { timestamp },
+                EventType::SDL_LOCALECHANGED => Event::LocaleChanged { timestamp },
+
+                EventType::SDL_DISPLAYEVENT => {
+                    let e = raw.display;
+                    Event::Display { timestamp, display_index: e.display, display_event: e.event, data1: e.data1 }
+                }
+                EventType::SDL_WINDOWEVENT => {
+                    let e = raw.window;
+                    Event::Window { timestamp, window_id: e.windowID, win_event: WindowEvent::from_ll(e.event, e.data1, e.data2) }
+                }
+
+                EventType::SDL_KEYDOWN => {
+                    let e = raw.key;
+                    Event::KeyDown {
+                        timestamp,
+                        window_id: e.windowID,
+                        scancode: Scancode::from_ll(e.keysym.scancode),
+                        keycode: Keycode::from_ll(e.keysym.sym),
+                        keymod: Mod::from_ll(e.keysym.mod_),
+                        repeat: e.repeat != 0,
+                    }
+                }
+                EventType::SDL_KEYUP => {
+                    let e = raw.key;
+                    Event::KeyUp {
+                        timestamp,
+                        window_id: e.windowID,
+                        scancode: Scancode::from_ll(e.keysym.scancode),
+                        keycode: Keycode::from_ll(e.keysym.sym),
+                        keymod: Mod::from_ll(e.keysym.mod_),
+                        repeat: e.repeat != 0,
+                    }
+                }
+                EventType::SDL_TEXTEDITING => {
+                    let e = raw.edit;
+                    Event::TextEditing { timestamp, window_id: e.windowID, text: c_str_to_string(e.text.as_ptr()), start: e.start, length: e.length }
+                }
+                EventType::SDL_TEXTEDITING_EXT => {
+                    // sent instead of SDL_TEXTEDITING for long texts with SDL_HINT_IME_SUPPORT_EXTENDED_TEXT
+                    let e = raw.editExt;
+                    Event::TextEditing { timestamp, window_id: e.windowID, text: c_str_to_string(e.text), start: e.start, length: e.length }
+                }
+                EventType::SDL_TEXTINPUT => {
+                    let e = raw.text;
+                    Event::TextInput { timestamp, window_id: e.windowID, text: c_str_to_string(e.text.as_ptr()) }
+                }
+                EventType::SDL_KEYMAPCHANGED => Event::KeymapChanged { timestamp },
+
+                EventType::SDL_MOUSEMOTION => {
+                    let e = raw.motion;
+                    Event::MouseMotion {
+                        timestamp,
+                        window_id: e.windowID,
+                        which: e.which,
+                        mousestate: MouseState::from_sdl_state(e.state),
+                        x: e.x,
+                        y: e.y,
+                        xrel: e.xrel,
+                        yrel: e.yrel,
+                    }
+                }
+                EventType::SDL_MOUSEBUTTONDOWN => {
+                    let e = raw.button;
+                    Event::MouseButtonDown {
+                        timestamp,
+                        window_id: e.windowID,
+                        which: e.which,
+                        mouse_btn: MouseButton::from_ll(e.button),
+                        clicks: e.clicks,
+                        x: e.x,
+                        y: e.y,
+                    }
+                }
+                EventType::SDL_MOUSEBUTTONUP => {
+                    let e = raw.button;
+                    Event::MouseButtonUp {
+                        timestamp,
+                        window_id: e.windowID,
+                        which: e.which,
+                        mouse_btn: MouseButton::from_ll(e.button),
+                        clicks: e.clicks,
+                        x: e.x,
+                        y: e.y,
+                    }
+                }
+                EventType::SDL_MOUSEWHEEL => {
+                    let e = raw.wheel;
+                    Event::MouseWheel {
+                        timestamp,
+                        window_id: e.windowID,
+                        which: e.which,
+                        x: e.x,
+                        y: e.y,
+                        precise_x: e.preciseX,
+                        precise_y: e.preciseY,
+                        flipped: e.direction == sdl::SDL_MouseWheelDirection::SDL_MOUSEWHEEL_FLIPPED as u32,
+                    }
+                }
+
+                EventType::SDL_CONTROLLERDEVICEADDED => Event::ControllerDeviceAdded { timestamp, which: raw.cdevice.which as u32 },
+                EventType::SDL_CONTROLLERDEVICEREMOVED => Event::ControllerDeviceRemoved { timestamp, which: raw.cdevice.which },
+                EventType::SDL_CONTROLLERDEVICEREMAPPED => Event::ControllerDeviceRemapped { timestamp, which: raw.cdevice.which },
+                EventType::SDL_CONTROLLERAXISMOTION => {
+                    let e = raw.caxis;
+                    match controller::Axis::from_ll(e.axis) {
+                        Some(axis) => Event::ControllerAxisMotion { timestamp, which: e.which, axis, value: e.value },
+                        None => Event::Unknown { timestamp, type_: raw.type_ },
+                    }
+                }
+                EventType::SDL_CONTROLLERBUTTONDOWN => {
+                    let e = raw.cbutton;
+                    match controller::Button::from_ll(e.button) {
+                        Some(button) => Event::ControllerButtonDown { timestamp, which: e.which, button },
+                        None => Event::Unknown { timestamp, type_: raw.type_ },
+                    }
+                }
+                EventType::SDL_CONTROLLERBUTTONUP => {
+                    let e = raw.cbutton;
+                    match controller::Button::from_ll(e.button) {
+                        Some(button) => Event::ControllerButtonUp { timestamp, which: e.which, button },
+                        None => Event::Unknown { timestamp, type_: raw.type_ },
+                    }
+                }
+
+                EventType::SDL_FINGERDOWN => {
+                    let e = raw.tfinger;
+                    Event::FingerDown {
+                        timestamp,
+                        window_id: e.windowID,
+                        touch_id: e.touchId,
+                        finger_id: e.fingerId,
+                        x: e.x,
+                        y: e.y,
+                        dx: e.dx,
+                        dy: e.dy,
+                        pressure: e.pressure,
+                    }
+                }
+                EventType::SDL_FINGERUP => {
+                    let e = raw.tfinger;
+                    Event::FingerUp {
+                        timestamp,
+                        window_id: e.windowID,
+                        touch_id: e.touchId,
+                        finger_id: e.fingerId,
+                        x: e.x,
+                        y: e.y,
+                        dx: e.dx,
+                        dy: e.dy,
+                        pressure: e.pressure,
+                    }
+                }
+                EventType::SDL_FINGERMOTION => {
+                    let e = raw.tfinger;
+                    Event::FingerMotion {
+                        timestamp,
+                        window_id: e.windowID,
+                        touch_id: e.touchId,
+                        finger_id: e.fingerId,
+                        x: e.x,
+                        y: e.y,
+                        dx: e.dx,
+                        dy: e.dy,
+                        pressure: e.pressure,
+                    }
+                }
+                EventType::SDL_MULTIGESTURE => {
+                    let e = raw.mgesture;
+                    Event::MultiGesture { timestamp, touch_id: e.touchId, d_theta: e.dTheta, d_dist: e.dDist, x: e.x, y: e.y, num_fingers: e.numFingers }
+                }
+                EventType::SDL_DOLLARGESTURE => {
+                    let e = raw.dgesture;
+                    Event::DollarGesture { timestamp, touch_id: e.touchId, gesture_id: e.gestureId, num_fingers: e.numFingers, error: e.error, x: e.x, y: e.y }
+                }
+                EventType::SDL_DOLLARRECORD => {
+                    let e = raw.dgesture;
+                    Event::DollarRecord { timestamp, touch_id: e.touchId, gesture_id: e.gestureId }
+                }
+
+                EventType::SDL_AUDIODEVICEADDED => {
+                    let e = raw.adevice;
+                    Event::AudioDeviceAdded { timestamp, which: e.which, iscapture: e.iscapture != 0 }
+                }
+                EventType::SDL_AUDIODEVICEREMOVED => {
+                    let e = raw.adevice;
+                    Event::AudioDeviceRemoved { timestamp, which: e.which, iscapture: e.iscapture != 0 }
+                }
+
+                EventType::SDL_CLIPBOARDUPDATE => Event::ClipboardUpdate { timestamp },
+
+                EventType::SDL_DROPFILE => {
+                    let e = raw.drop;
+                    Event::DropFile { timestamp, window_id: e.windowID, filename: c_str_to_string(e.file) }
+                }
+                EventType::SDL_DROPTEXT => {
+                    let e = raw.drop;
+                    Event::DropText { timestamp, window_id: e.windowID, text: c_str_to_string(e.file) }
+                }
+                EventType::SDL_DROPBEGIN => Event::DropBegin { timestamp, window_id: raw.drop.windowID },
+                EventType::SDL_DROPCOMPLETE => Event::DropComplete { timestamp, window_id: raw.drop.windowID },
+
+                EventType::SDL_RENDER_TARGETS_RESET => Event::RenderTargetsReset { timestamp },
+                EventType::SDL_RENDER_DEVICE_RESET => Event::RenderDeviceReset { timestamp },
+
+                _ => Event::Unknown { timestamp, type_: raw.type_ },
+            }
+        }
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_quit(&self) -> bool {
+        matches!(self, Event::Quit { .. })
+    }
+
+    /// Returns the payload if this is a custom event carrying a `T`.
+    pub fn as_custom<T: Any>(&self) -> Option<&T> {
+        match self {
+            Event::Custom { payload, .. } => payload.downcast_ref::<T>(),
+            _ => None,
+        }
+    }
+}
+
+/// Frees the memory owned by a raw event: the `SDL_DROPFILE`/`SDL_DROPTEXT` and
+/// `SDL_TEXTEDITING_EXT` strings and the boxed payload of custom events.
+///
+/// Must be called exactly once for every such event taken out of the queue.
+pub(crate) unsafe fn free_raw_event(raw: &RawEvent) {
+    let t = raw.type_;
+    if t == sdl::SDL_EventType::SDL_DROPFILE as u32 || t == sdl::SDL_EventType::SDL_DROPTEXT as u32 {
+        sdl::SDL_free(raw.drop.file as *mut _);
+    } else if t == sdl::SDL_EventType::SDL_TEXTEDITING_EXT as u32 {
+        sdl::SDL_free(raw.editExt.text as *mut _);
+    } else if is_custom_event_id(t) && !raw.user.data1.is_null() {
+        drop(Box::from_raw(raw.user.data1 as *mut Arc<dyn Any + Send + Sync>));
+    }
+}
+
+/// The value carried by `Event::Custom`, shared between clones of the event.
+#[derive(Clone)]
+pub struct CustomPayload(Arc<dyn Any + Send + Sync>);
+
+impl PartialEq for CustomPayload {
+    fn eq(&self, other: &CustomPayload) -> bool {
+        Arc::as_ptr(&self.0) as *const () == Arc::as_ptr(&other.0) as *const ()
+    }
+}
+
+impl std::fmt::Debug for CustomPayload {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        return write!(fmt, "CustomPayload({:?})", self.0.type_id());
     }
 }
+
+impl CustomPayload {
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref::<T>()
+    }
+}
+
+static CUSTOM_EVENT_TYPES: Mutex<Vec<(TypeId, u32)>> = Mutex::new(Vec::new());
+
+fn custom_event_id<T: Any>() -> Option<u32> {
+    let types = CUSTOM_EVENT_TYPES.lock().unwrap();
+    return types.iter().find(|(type_id, _)| *type_id == TypeId::of::<T>()).map(|(_, id)| *id);
+}
+
+fn is_custom_event_id(id: u32) -> bool {
+    let types = CUSTOM_EVENT_TYPES.lock().unwrap();
+    return types.iter().any(|(_, registered)| *registered == id);
+}
+
+/// Reserves `count` consecutive user event ids and returns the first one.
+#[doc(alias = "SDL_RegisterEvents")]
+pub fn register_events(count: i32) -> anyhow::Result<u32> {
+    let id = unsafe { sdl::SDL_RegisterEvents(count) };
+    if id == u32::MAX {
+        return Err(anyhow::anyhow!("Not enough user-defined events left {}({},{})", file!(), line!(), column!()));
+    }
+
+    return Ok(id);
+}
+
+/// Registers `T` as a custom event payload and returns its event id.
+///
+/// Registering the same type again returns the id it already has.
+pub fn register_custom_event<T: Any + Send + Sync>() -> anyhow::Result<u32> {
+    // one lock for the lookup and the registration, or two threads could get different ids for `T`
+    let mut types = CUSTOM_EVENT_TYPES.lock().unwrap();
+    if let Some((_, id)) = types.iter().find(|(type_id, _)| *type_id == TypeId::of::<T>()) {
+        return Ok(*id);
+    }
+
+    let id = register_events(1)?;
+    types.push((TypeId::of::<T>(), id));
+    return Ok(id);
+}
+
+/// Pushes `payload` onto the event queue, it comes back out as `Event::Custom`.
+///
+/// `T` must be registered with `register_custom_event` first. Safe to call from any
+/// thread, including `timer::Timer` callbacks.
+///
+/// Payloads still in the queue when SDL shuts down are leaked, and so are those of events taken
+/// out of the queue without `poll_event`, `wait_event` or `filter_events`, e.g. by
+/// `SDL_FlushEvent(s)` or `SDL_PeepEvents`.
+#[doc(alias = "SDL_PushEvent")]
+pub fn push_custom_event<T: Any + Send + Sync>(payload: T) -> anyhow::Result<()> {
+    let type_ = match custom_event_id::<T>() {
+        Some(id) => id,
+        None => {
+            return Err(anyhow::anyhow!("{} is not a registered custom event {}({},{})", std::any::type_name::<T>(), file!(), line!(), column!()));
+        }
+    };
+
+    let payload: Box<Arc<dyn Any + Send + Sync>> = Box::new(Arc::new(payload));
+    let data1 = Box::into_raw(payload) as *mut c_void;
+
+    let mut raw: RawEvent = unsafe { std::mem::zeroed() };
+    raw.user = sdl::SDL_UserEvent { type_, timestamp: 0, windowID: 0, code: 0, data1, data2: std::ptr::null_mut() };
+
+    unsafe {
+        let ret = sdl::SDL_PushEvent(&mut raw);
+        if ret != 1 {
+            // filtered out or failed, either way the queue never took the payload
+            drop(Box::from_raw(data1 as *mut Arc<dyn Any + Send + Sync>));
+        }
+        if ret < 0 {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+        }
+    }
+
+    return Ok(());
+}
+
+/// Polls the raw `SDL_Event`. The caller owns any data inside it (the `file` pointer
+/// of drop events, the `text` of `SDL_TEXTEDITING_EXT`, the payload of custom events) and is
+/// responsible for freeing it.
 #[inline]
-pub fn poll_event() -> Option<Event> {
+pub fn poll_event_raw() -> Option<RawEvent> {
     let mut event = std::mem::MaybeUninit::uninit();
     unsafe {
         if sdl::SDL_PollEvent(event.as_mut_ptr()) != 0 {
@@ -136,6 +680,218 @@ pub fn poll_event() -> Option<Event> {
     return None;
 }
 
+#[inline]
+pub fn poll_event() -> Option<Event> {
+    let raw = poll_event_raw()?;
+    let event = Event::from_ll(&raw);
+    unsafe { free_raw_event(&raw) };
+
+    return Some(event);
+}
+
+/// Blocks the current thread until an event is available.
+#[doc(alias = "SDL_WaitEvent")]
+pub fn wait_event() -> anyhow::Result<Event> {
+    let mut raw = std::mem::MaybeUninit::uninit();
+    unsafe {
+        if sdl::SDL_WaitEvent(raw.as_mut_ptr()) == 0 {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+        }
+
+        let raw = raw.assume_init();
+        let event = Event::from_ll(&raw);
+        free_raw_event(&raw);
+        return Ok(event);
+    }
+}
+
+/// Blocks the current thread until an event is available or `timeout` milliseconds passed.
+///
+/// Returns `None` on timeout (SDL does not tell a timeout apart from an error here).
+/// Timeouts above `i32::MAX` are clamped, SDL would take them as negative and wait forever.
+#[doc(alias = "SDL_WaitEventTimeout")]
+pub fn wait_event_timeout(timeout: u32) -> Option<Event> {
+    let mut raw = std::mem::MaybeUninit::uninit();
+    unsafe {
+        if sdl::SDL_WaitEventTimeout(raw.as_mut_ptr(), timeout.min(i32::MAX as u32) as i32) == 0 {
+            return None;
+        }
+
+        let raw = raw.assume_init();
+        let event = Event::from_ll(&raw);
+        free_raw_event(&raw);
+        return Some(event);
+    }
+}
+
+/// Iterates over the pending events without blocking, ends when the queue is empty.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub fn poll_iter() -> EventPollIterator {
+    EventPollIterator {}
+}
+
+/// Iterates over the events forever, blocking the thread while the queue is empty.
+///
+/// Ends only if `SDL_WaitEvent` fails.
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub fn wait_iter() -> EventWaitIterator {
+    EventWaitIterator {}
+}
+
+pub struct EventPollIterator;
+
+impl Iterator for EventPollIterator {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        poll_event()
+    }
+}
+
+pub struct EventWaitIterator;
+
+impl Iterator for EventWaitIterator {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        wait_event().ok()
+    }
+}
+
+pub type EventFilterCallback<'a> = Box<dyn FnMut(&Event) -> bool + 'a + Send>;
+pub type EventWatchCallback<'a> = Box<dyn FnMut(&Event) + 'a + Send>;
+
+/// Installs `callback` as the event filter, it runs before an event is added to the queue
+/// and the event is dropped when it returns `false`.
+///
+/// SDL only keeps one filter: installing another replaces this one. The filter is removed
+/// when the returned guard is dropped (unless it was already replaced).
+///
+/// Installing the filter discards every event already in the queue (SDL flushes it), not only
+/// the ones the filter would reject; their payloads are freed.
+///
+/// The callback may run on any thread that pushes events. The callback *must* not panic!
+#[doc(alias = "SDL_SetEventFilter")]
+pub fn set_event_filter<'a>(callback: EventFilterCallback<'a>) -> EventFilter<'a> {
+    // SDL_SetEventFilter flushes the queue without freeing what the events own, empty it first
+    filter_events(|_| false);
+
+    let mut callback = Box::new(callback);
+    let userdata = &mut *callback as *mut EventFilterCallback<'a> as *mut c_void;
+    unsafe { sdl::SDL_SetEventFilter(Some(c_event_filter), userdata) };
+
+    return EventFilter { callback: Some(callback) };
+}
+
+pub struct EventFilter<'a> {
+    callback: Option<Box<EventFilterCallback<'a>>>,
+}
+
+impl<'a> EventFilter<'a> {
+    /// Returns the closure as a trait-object and removes the filter
+    /// by consuming it...
+    pub fn into_inner(mut self) -> EventFilterCallback<'a> {
+        self.remove();
+        *self.callback.take().unwrap()
+    }
+
+    fn remove(&mut self) {
+        let callback = match self.callback.as_mut() {
+            Some(callback) => callback,
+            None => return,
+        };
+        let userdata = &mut **callback as *mut EventFilterCallback<'a> as *mut c_void;
+
+        unsafe {
+            let mut current_filter: sdl::SDL_EventFilter = None;
+            let mut current_userdata = std::ptr::null_mut();
+            // don't clear a filter somebody installed after us
+            if sdl::SDL_GetEventFilter(&mut current_filter, &mut current_userdata) == sdl::SDL_bool::SDL_TRUE && current_userdata == userdata {
+                sdl::SDL_SetEventFilter(None, std::ptr::null_mut());
+            }
+        }
+    }
+}
+
+impl<'a> Drop for EventFilter<'a> {
+    #[doc(alias = "SDL_SetEventFilter")]
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+/// Adds `callback` as an event watcher, it sees every event as it is added to the queue
+/// but cannot drop it. Removed when the returned guard is dropped.
+///
+/// The callback may run on any thread that pushes events. The callback *must* not panic!
+#[doc(alias = "SDL_AddEventWatch")]
+pub fn add_event_watch<'a>(callback: EventWatchCallback<'a>) -> EventWatch<'a> {
+    let mut callback = Box::new(callback);
+    let userdata = &mut *callback as *mut EventWatchCallback<'a> as *mut c_void;
+    unsafe { sdl::SDL_AddEventWatch(Some(c_event_watch), userdata) };
+
+    return EventWatch { callback: Some(callback) };
+}
+
+pub struct EventWatch<'a> {
+    callback: Option<Box<EventWatchCallback<'a>>>,
+}
+
+impl<'a> EventWatch<'a> {
+    /// Returns the closure as a trait-object and removes the watcher
+    /// by consuming it...
+    pub fn into_inner(mut self) -> EventWatchCallback<'a> {
+        self.remove();
+        *self.callback.take().unwrap()
+    }
+
+    fn remove(&mut self) {
+        if let Some(callback) = self.callback.as_mut() {
+            let userdata = &mut **callback as *mut EventWatchCallback<'a> as *mut c_void;
+            unsafe { sdl::SDL_DelEventWatch(Some(c_event_watch), userdata) };
+        }
+    }
+}
+
+impl<'a> Drop for EventWatch<'a> {
+    #[doc(alias = "SDL_DelEventWatch")]
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+/// Runs `filter` once over the events currently in the queue, removing those for
+/// which it returns `false`.
+#[doc(alias = "SDL_FilterEvents")]
+pub fn filter_events<F: FnMut(&Event) -> bool>(mut filter: F) {
+    let mut filter: &mut dyn FnMut(&Event) -> bool = &mut filter;
+    unsafe { sdl::SDL_FilterEvents(Some(c_filter_events), &mut filter as *mut &mut dyn FnMut(&Event) -> bool as *mut c_void) };
+}
+
+// FIXME: Same as `timer::c_timer_callback`, unwinding out of these is UB.
+extern "C" fn c_event_filter(userdata: *mut c_void, event: *mut sdl::SDL_Event) -> i32 {
+    let f = userdata as *mut EventFilterCallback<'_>;
+    unsafe { (*f)(&Event::from_ll(&*event)) as i32 }
+}
+
+extern "C" fn c_event_watch(userdata: *mut c_void, event: *mut sdl::SDL_Event) -> i32 {
+    let f = userdata as *mut EventWatchCallback<'_>;
+    unsafe { (*f)(&Event::from_ll(&*event)) };
+    return 0;
+}
+
+extern "C" fn c_filter_events(userdata: *mut c_void, event: *mut sdl::SDL_Event) -> i32 {
+    let f = userdata as *mut &mut dyn FnMut(&Event) -> bool;
+    unsafe {
+        let keep = (*f)(&Event::from_ll(&*event));
+        if !keep {
+            // the event is cut out of the queue, nobody else will free what it owns
+            free_raw_event(&*event);
+        }
+        return keep as i32;
+    }
+}
+
 pub struct KeyboardState<'a> {
     keyboard_state: &'a [u8],
 }
@@ -150,8 +906,8 @@ impl<'a> KeyboardState<'a> {
         }
     }
 
-    pub fn is_scancode_pressed(&self, scancode: sdl::SDL_Scancode) -> bool {
-        return self.keyboard_state[scancode as usize] != 0;
+    pub fn is_scancode_pressed(&self, scancode: Scancode) -> bool {
+        return self.keyboard_state[scancode.index()] != 0;
     }
 
 
@@ -183,7 +939,7 @@ impl MouseButton {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct MouseState {
     pub mouse_state: u32,
     pub x: i32,
@@ -201,6 +957,33 @@ impl MouseState {
             y: y as i32,
         }
     }
+
+    /// Buttons and cursor position relative to the focused window.
+    #[doc(alias = "SDL_GetMouseState")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn current() -> MouseState {
+        MouseState::new()
+    }
+
+    /// Buttons and the cursor motion since the last call, `x`/`y` hold the deltas.
+    ///
+    /// This is what FPS-style cameras want, together with `set_relative_mouse_mode(true)`.
+    #[doc(alias = "SDL_GetRelativeMouseState")]
+    pub fn relative() -> MouseState {
+        let mut x = 0;
+        let mut y = 0;
+        let mouse_state: u32 = unsafe { sdl::SDL_GetRelativeMouseState(&mut x, &mut y) };
+        MouseState { mouse_state, x, y }
+    }
+
+    /// Buttons and cursor position in desktop coordinates, also outside of the windows.
+    #[doc(alias = "SDL_GetGlobalMouseState")]
+    pub fn global() -> MouseState {
+        let mut x = 0;
+        let mut y = 0;
+        let mouse_state: u32 = unsafe { sdl::SDL_GetGlobalMouseState(&mut x, &mut y) };
+        MouseState { mouse_state, x, y }
+    }
     #[cfg_attr(not(debug_assertions), inline(always))]
     pub fn from_sdl_state(state: u32) -> MouseState {
         MouseState {
@@ -242,4 +1025,174 @@ impl MouseState {
         let mask = 1 << ((mouse_button as u32) - 1);
         self.mouse_state & mask != 0
     }
-}
\ No newline at end of file
+}
+/// Keyboard and mouse button levels of the current and the previous frame,
+/// for edge detection (`just_pressed` / `just_released`).
+///
+/// Call `update` once per frame after polling the events.
+pub struct InputSnapshot {
+    keys: Vec<u8>,
+    previous_keys: Vec<u8>,
+    mouse: MouseState,
+    previous_mouse: MouseState,
+}
+
+impl InputSnapshot {
+    pub fn new() -> InputSnapshot {
+        let keys = KeyboardState::new().keyboard_state.to_vec();
+        let mouse = MouseState::new();
+        InputSnapshot { previous_keys: keys.clone(), keys, previous_mouse: mouse, mouse }
+    }
+
+    /// Moves the current state to the previous frame and samples SDL again.
+    pub fn update(&mut self) {
+        std::mem::swap(&mut self.keys, &mut self.previous_keys);
+        self.keys.clear();
+        self.keys.extend_from_slice(KeyboardState::new().keyboard_state);
+
+        self.previous_mouse = self.mouse;
+        self.mouse = MouseState::new();
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    fn key(keys: &[u8], scancode: Scancode) -> bool {
+        keys.get(scancode.index()).is_some_and(|k| *k != 0)
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    fn button(mouse: &MouseState, button: MouseButton) -> bool {
+        button != MouseButton::Unknown && mouse.is_mouse_button_pressed(button)
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_pressed(&self, scancode: Scancode) -> bool {
+        InputSnapshot::key(&self.keys, scancode)
+    }
+
+    /// True only on the frame the key went down.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn just_pressed(&self, scancode: Scancode) -> bool {
+        InputSnapshot::key(&self.keys, scancode) && !InputSnapshot::key(&self.previous_keys, scancode)
+    }
+
+    /// True only on the frame the key went up.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn just_released(&self, scancode: Scancode) -> bool {
+        !InputSnapshot::key(&self.keys, scancode) && InputSnapshot::key(&self.previous_keys, scancode)
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        InputSnapshot::button(&self.mouse, button)
+    }
+
+    /// True only on the frame the button went down.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn mouse_just_pressed(&self, button: MouseButton) -> bool {
+        InputSnapshot::button(&self.mouse, button) && !InputSnapshot::button(&self.previous_mouse, button)
+    }
+
+    /// True only on the frame the button went up.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn mouse_just_released(&self, button: MouseButton) -> bool {
+        !InputSnapshot::button(&self.mouse, button) && InputSnapshot::button(&self.previous_mouse, button)
+    }
+
+    /// The mouse state sampled by the last `update`.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn mouse(&self) -> &MouseState {
+        &self.mouse
+    }
+
+    /// The mouse state sampled by the `update` before the last one.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn previous_mouse(&self) -> &MouseState {
+        &self.previous_mouse
+    }
+}
+
+/// Hides the cursor and reports only relative motion, the cursor stays put even at the window border.
+#[doc(alias = "SDL_SetRelativeMouseMode")]
+pub fn set_relative_mouse_mode(enabled: bool) -> anyhow::Result<()> {
+    let enabled = if enabled { sdl::SDL_bool::SDL_TRUE } else { sdl::SDL_bool::SDL_FALSE };
+    unsafe {
+        if sdl::SDL_SetRelativeMouseMode(enabled) != 0 {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+        }
+    }
+
+    return Ok(());
+}
+
+#[doc(alias = "SDL_GetRelativeMouseMode")]
+pub fn relative_mouse_mode() -> bool {
+    unsafe { sdl::SDL_GetRelativeMouseMode() == sdl::SDL_bool::SDL_TRUE }
+}
+
+/// Moves the cursor to desktop coordinates.
+#[doc(alias = "SDL_WarpMouseGlobal")]
+pub fn warp_mouse_global(x: i32, y: i32) -> anyhow::Result<()> {
+    unsafe {
+        if sdl::SDL_WarpMouseGlobal(x, y) != 0 {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+        }
+    }
+
+    return Ok(());
+}
+
+/// Keeps delivering mouse events to the focused window while the cursor is outside of it,
+/// e.g. while dragging.
+#[doc(alias = "SDL_CaptureMouse")]
+pub fn capture_mouse(enabled: bool) -> anyhow::Result<()> {
+    let enabled = if enabled { sdl::SDL_bool::SDL_TRUE } else { sdl::SDL_bool::SDL_FALSE };
+    unsafe {
+        if sdl::SDL_CaptureMouse(enabled) != 0 {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+        }
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_is_send_and_sync() {
+        fn check<T: Send + Sync>() {}
+        check::<Event>();
+    }
+
+    #[test]
+    fn set_event_filter_frees_queued_events() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        unsafe { assert_eq!(sdl::SDL_InitSubSystem(sdl::SDL_INIT_EVENTS), 0) };
+
+        register_custom_event::<Arc<u32>>().unwrap();
+        register_custom_event::<Arc<i64>>().unwrap();
+        let queued = Arc::new(1u32);
+        push_custom_event(queued.clone()).unwrap();
+        assert_eq!(Arc::strong_count(&queued), 2);
+
+        let filter = set_event_filter(Box::new(|event: &Event| event.as_custom::<Arc<u32>>().is_none()));
+        assert_eq!(Arc::strong_count(&queued), 1);
+        assert!(poll_event().is_none());
+
+        let rejected = Arc::new(3u32);
+        let kept = Arc::new(4i64);
+        push_custom_event(rejected.clone()).unwrap();
+        push_custom_event(kept.clone()).unwrap();
+        assert_eq!(Arc::strong_count(&rejected), 1);
+
+        let event = poll_event().unwrap();
+        assert_eq!(event.as_custom::<Arc<i64>>().map(|v| **v), Some(4));
+        drop(event);
+        assert!(poll_event().is_none());
+        assert_eq!(Arc::strong_count(&kept), 1);
+
+        drop(filter);
+        unsafe { sdl::SDL_QuitSubSystem(sdl::SDL_INIT_EVENTS) };
+    }
+}
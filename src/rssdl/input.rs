@@ -0,0 +1,506 @@
+use std::io::{Read, Write};
+
+use anyhow;
+
+use crate::{
+    controller::{self, GameController},
+    events::{KeyboardState, MouseButton, MouseState, Scancode},
+    rwops::RWops,
+};
+
+/// A physical input an action or axis can be bound to.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Binding {
+    Key(Scancode),
+    Mouse(MouseButton),
+    /// A button of the controller passed to `InputMap::update_with_controller`.
+    ControllerButton(controller::Button),
+}
+
+impl Binding {
+    /// Keys without a name that maps back to them are written by number, `key #<scancode>`.
+    fn to_config(self) -> String {
+        match self {
+            Binding::Key(scancode) => {
+                let name = scancode.name();
+                if !name.is_empty() && Scancode::from_name(&name) == Some(scancode) {
+                    format!("key {}", name)
+                } else {
+                    format!("key #{}", scancode.raw as i32)
+                }
+            }
+            Binding::Mouse(button) => format!("mouse {}", mouse_button_name(button)),
+            Binding::ControllerButton(button) => format!("button {:?}", button),
+        }
+    }
+
+    fn from_config(text: &str) -> Option<Binding> {
+        let (kind, name) = text.split_once(' ')?;
+        match kind {
+            // "#" alone is the name of the non-US hash key
+            "key" => match name.strip_prefix('#').and_then(|code| code.parse().ok()) {
+                Some(code) => Scancode::from_i32(code).map(Binding::Key),
+                None => Scancode::from_name(name).map(Binding::Key),
+            },
+            "mouse" => mouse_button_from_name(name).map(Binding::Mouse),
+            "button" => controller_button_from_name(name).map(Binding::ControllerButton),
+            _ => None,
+        }
+    }
+}
+
+/// Controller buttons and axes are written with their `Debug` names, e.g. `DPadLeft`.
+fn controller_button_from_name(name: &str) -> Option<controller::Button> {
+    (0..=u8::MAX).filter_map(controller::Button::from_ll).find(|button| format!("{:?}", button) == name)
+}
+
+fn controller_axis_from_name(name: &str) -> Option<controller::Axis> {
+    (0..=u8::MAX).filter_map(controller::Axis::from_ll).find(|axis| format!("{:?}", axis) == name)
+}
+
+fn mouse_button_name(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Unknown => "Unknown",
+        MouseButton::Left => "Left",
+        MouseButton::Middle => "Middle",
+        MouseButton::Right => "Right",
+        MouseButton::X1 => "X1",
+        MouseButton::X2 => "X2",
+    }
+}
+
+fn mouse_button_from_name(name: &str) -> Option<MouseButton> {
+    match name {
+        "Unknown" => Some(MouseButton::Unknown),
+        "Left" => Some(MouseButton::Left),
+        "Middle" => Some(MouseButton::Middle),
+        "Right" => Some(MouseButton::Right),
+        "X1" => Some(MouseButton::X1),
+        "X2" => Some(MouseButton::X2),
+        _ => None,
+    }
+}
+
+/// Something that moves an axis.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum AxisSource {
+    /// Pushes the axis to -1.0 while held.
+    Negative(Binding),
+    /// Pushes the axis to 1.0 while held.
+    Positive(Binding),
+    /// Horizontal cursor movement since the last update, multiplied by the scale.
+    MouseX(f32),
+    /// Vertical cursor movement since the last update, multiplied by the scale.
+    MouseY(f32),
+    /// A stick or trigger of the controller passed to `InputMap::update_with_controller`, in `-1.0..=1.0`.
+    ControllerAxis(controller::Axis),
+}
+
+struct Action {
+    name: String,
+    bindings: Vec<Binding>,
+    was_down: bool,
+    is_down: bool,
+}
+
+struct Axis {
+    name: String,
+    sources: Vec<AxisSource>,
+    dead_zone: f32,
+    value: f32,
+}
+
+/// Maps named actions ("jump", "fire") and axes ("move_x") to keys, mouse and controller buttons.
+///
+/// Call `update` (or `update_with_controller`) once per frame, then query `pressed`/`held`/`released` and `axis`.
+///
+/// Bindings can be stored in a line based text config:
+///
+/// ```text
+/// # comment
+/// action jump key Space
+/// action jump mouse Left
+/// axis move_x negative key A
+/// axis move_x positive key D
+/// axis look_x mouse_x 0.01
+/// deadzone look_x 0.1
+/// action jump button A
+/// axis move_x controller LeftX
+/// ```
+///
+/// Action and axis names must not contain spaces, key names are the ones from `Scancode::name`,
+/// `key #<scancode>` binds a key by number. Controller buttons and axes use the names of
+/// `controller::Button` and `controller::Axis`.
+pub struct InputMap {
+    actions: Vec<Action>,
+    axes: Vec<Axis>,
+    mouse_position: Option<(i32, i32)>,
+}
+
+impl InputMap {
+    pub fn new() -> InputMap {
+        InputMap { actions: Vec::new(), axes: Vec::new(), mouse_position: None }
+    }
+
+    fn action_mut(&mut self, name: &str) -> &mut Action {
+        let index = match self.actions.iter().position(|a| a.name == name) {
+            Some(index) => index,
+            None => {
+                self.actions.push(Action { name: name.to_owned(), bindings: Vec::new(), was_down: false, is_down: false });
+                self.actions.len() - 1
+            }
+        };
+
+        return &mut self.actions[index];
+    }
+
+    fn axis_mut(&mut self, name: &str) -> &mut Axis {
+        let index = match self.axes.iter().position(|a| a.name == name) {
+            Some(index) => index,
+            None => {
+                self.axes.push(Axis { name: name.to_owned(), sources: Vec::new(), dead_zone: 0.0, value: 0.0 });
+                self.axes.len() - 1
+            }
+        };
+
+        return &mut self.axes[index];
+    }
+
+    /// Adds `binding` to `action`, an action is down while any of its bindings is.
+    pub fn bind(&mut self, action: &str, binding: Binding) {
+        let action = self.action_mut(action);
+        if !action.bindings.contains(&binding) {
+            action.bindings.push(binding);
+        }
+    }
+
+    pub fn unbind(&mut self, action: &str, binding: Binding) {
+        if let Some(action) = self.actions.iter_mut().find(|a| a.name == action) {
+            action.bindings.retain(|b| *b != binding);
+        }
+    }
+
+    /// Removes every binding of `action`, e.g. before rebinding it.
+    pub fn clear_bindings(&mut self, action: &str) {
+        if let Some(action) = self.actions.iter_mut().find(|a| a.name == action) {
+            action.bindings.clear();
+        }
+    }
+
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        match self.actions.iter().find(|a| a.name == action) {
+            Some(action) => &action.bindings,
+            None => &[],
+        }
+    }
+
+    /// Adds `source` to `axis`, the axis value is the clamped sum of its sources.
+    pub fn bind_axis(&mut self, axis: &str, source: AxisSource) {
+        let axis = self.axis_mut(axis);
+        if !axis.sources.contains(&source) {
+            axis.sources.push(source);
+        }
+    }
+
+    pub fn clear_axis(&mut self, axis: &str) {
+        if let Some(axis) = self.axes.iter_mut().find(|a| a.name == axis) {
+            axis.sources.clear();
+        }
+    }
+
+    /// Values with a magnitude below `dead_zone` read as 0.0, the rest is rescaled to keep the full range.
+    pub fn set_dead_zone(&mut self, axis: &str, dead_zone: f32) {
+        self.axis_mut(axis).dead_zone = dead_zone.clamp(0.0, 0.99);
+    }
+
+    /// Samples the bindings, call it once per frame after the events were polled.
+    ///
+    /// Controller bindings read as released, see `update_with_controller`.
+    pub fn update(&mut self, keyboard: &KeyboardState, mouse: &MouseState) {
+        self.update_with_controller(keyboard, mouse, None);
+    }
+
+    /// Like `update`, the controller bindings are read from `controller`.
+    pub fn update_with_controller(&mut self, keyboard: &KeyboardState, mouse: &MouseState, controller: Option<&GameController<'_>>) {
+        let is_down = |binding: Binding| match binding {
+            Binding::Key(scancode) => keyboard.is_scancode_pressed(scancode),
+            Binding::Mouse(MouseButton::Unknown) => false,
+            Binding::Mouse(button) => mouse.is_mouse_button_pressed(button),
+            Binding::ControllerButton(button) => controller.is_some_and(|c| c.button(button)),
+        };
+        // -32768 would reach a bit past -1.0
+        let axis_value = |axis: controller::Axis| controller.map_or(0.0, |c| (c.axis(axis) as f32 / 32767.0).max(-1.0));
+
+        self.sample(is_down, axis_value, (mouse.x, mouse.y));
+    }
+
+    /// Moves the actions and axes to the state of the bindings, `mouse` is the cursor position.
+    fn sample<D, A>(&mut self, is_down: D, axis_value: A, mouse: (i32, i32))
+    where
+        D: Fn(Binding) -> bool,
+        A: Fn(controller::Axis) -> f32,
+    {
+        for action in self.actions.iter_mut() {
+            action.was_down = action.is_down;
+            action.is_down = action.bindings.iter().any(|b| is_down(*b));
+        }
+
+        let (dx, dy) = match self.mouse_position {
+            Some((x, y)) => ((mouse.0 - x) as f32, (mouse.1 - y) as f32),
+            None => (0.0, 0.0),
+        };
+        self.mouse_position = Some(mouse);
+
+        for axis in self.axes.iter_mut() {
+            let mut value = 0.0;
+            for source in axis.sources.iter() {
+                value += match *source {
+                    AxisSource::Negative(binding) if is_down(binding) => -1.0,
+                    AxisSource::Positive(binding) if is_down(binding) => 1.0,
+                    AxisSource::MouseX(scale) => dx * scale,
+                    AxisSource::MouseY(scale) => dy * scale,
+                    AxisSource::ControllerAxis(controller_axis) => axis_value(controller_axis),
+                    _ => 0.0,
+                };
+            }
+            let value: f32 = value.clamp(-1.0, 1.0);
+
+            axis.value = if value.abs() < axis.dead_zone { 0.0 } else { value.signum() * (value.abs() - axis.dead_zone) / (1.0 - axis.dead_zone) };
+        }
+    }
+
+    /// True on the frame the action went down.
+    pub fn pressed(&self, action: &str) -> bool {
+        self.actions.iter().any(|a| a.name == action && a.is_down && !a.was_down)
+    }
+
+    /// True while the action is down.
+    pub fn held(&self, action: &str) -> bool {
+        self.actions.iter().any(|a| a.name == action && a.is_down)
+    }
+
+    /// True on the frame the action went up.
+    pub fn released(&self, action: &str) -> bool {
+        self.actions.iter().any(|a| a.name == action && !a.is_down && a.was_down)
+    }
+
+    /// The axis value in `-1.0..=1.0`, 0.0 for unknown axes.
+    pub fn axis(&self, axis: &str) -> f32 {
+        match self.axes.iter().find(|a| a.name == axis) {
+            Some(axis) => axis.value,
+            None => 0.0,
+        }
+    }
+
+    pub fn from_config(config: &str) -> anyhow::Result<InputMap> {
+        let mut map = InputMap::new();
+
+        for (number, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || anyhow::anyhow!("Invalid input config line {}: {:?} {}({},{})", number + 1, line, file!(), line!(), column!());
+
+            let mut parts = line.splitn(3, ' ');
+            let kind = parts.next().ok_or_else(invalid)?;
+            let name = parts.next().ok_or_else(invalid)?;
+            let rest = parts.next().ok_or_else(invalid)?.trim();
+
+            match kind {
+                "action" => {
+                    let binding = Binding::from_config(rest).ok_or_else(invalid)?;
+                    map.bind(name, binding);
+                }
+                "axis" => {
+                    let (source, value) = rest.split_once(' ').ok_or_else(invalid)?;
+                    let source = match source {
+                        "negative" => AxisSource::Negative(Binding::from_config(value).ok_or_else(invalid)?),
+                        "positive" => AxisSource::Positive(Binding::from_config(value).ok_or_else(invalid)?),
+                        "mouse_x" => AxisSource::MouseX(value.parse().map_err(|_| invalid())?),
+                        "mouse_y" => AxisSource::MouseY(value.parse().map_err(|_| invalid())?),
+                        "controller" => AxisSource::ControllerAxis(controller_axis_from_name(value).ok_or_else(invalid)?),
+                        _ => return Err(invalid()),
+                    };
+                    map.bind_axis(name, source);
+                }
+                "deadzone" => {
+                    map.set_dead_zone(name, rest.parse().map_err(|_| invalid())?);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        return Ok(map);
+    }
+
+    pub fn to_config(&self) -> String {
+        let mut config = String::new();
+
+        for action in self.actions.iter() {
+            for binding in action.bindings.iter() {
+                config.push_str(&format!("action {} {}\n", action.name, binding.to_config()));
+            }
+        }
+
+        for axis in self.axes.iter() {
+            for source in axis.sources.iter() {
+                let source = match *source {
+                    AxisSource::Negative(binding) => format!("negative {}", binding.to_config()),
+                    AxisSource::Positive(binding) => format!("positive {}", binding.to_config()),
+                    AxisSource::MouseX(scale) => format!("mouse_x {}", scale),
+                    AxisSource::MouseY(scale) => format!("mouse_y {}", scale),
+                    AxisSource::ControllerAxis(controller_axis) => format!("controller {:?}", controller_axis),
+                };
+                config.push_str(&format!("axis {} {}\n", axis.name, source));
+            }
+            if axis.dead_zone != 0.0 {
+                config.push_str(&format!("deadzone {} {}\n", axis.name, axis.dead_zone));
+            }
+        }
+
+        return config;
+    }
+
+    pub fn load(rwops: &mut RWops) -> anyhow::Result<InputMap> {
+        let mut config = String::new();
+        if let Err(ioerror) = rwops.read_to_string(&mut config) {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", ioerror, file!(), line!(), column!()));
+        }
+
+        return InputMap::from_config(&config);
+    }
+
+    pub fn save(&self, rwops: &mut RWops) -> anyhow::Result<()> {
+        if let Err(ioerror) = rwops.write_all(self.to_config().as_bytes()) {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", ioerror, file!(), line!(), column!()));
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sdl2_sys as sdl;
+
+    use super::*;
+
+    #[test]
+    fn config_round_trip() {
+        let bindings = [
+            Binding::Key(Scancode::from_ll(sdl::SDL_Scancode::SDL_SCANCODE_SPACE)),
+            Binding::Key(Scancode::from_ll(sdl::SDL_Scancode::SDL_SCANCODE_LSHIFT)),
+            // named "#"
+            Binding::Key(Scancode::from_ll(sdl::SDL_Scancode::SDL_SCANCODE_NONUSHASH)),
+            // no name at all
+            Binding::Key(Scancode::from_ll(sdl::SDL_Scancode::SDL_SCANCODE_UNKNOWN)),
+            Binding::Key(Scancode::from_ll(sdl::SDL_Scancode::SDL_SCANCODE_LANG9)),
+            Binding::Mouse(MouseButton::Left),
+            Binding::Mouse(MouseButton::Unknown),
+        ];
+
+        let mut map = InputMap::new();
+        for binding in bindings.iter() {
+            map.bind("jump", *binding);
+        }
+        map.bind_axis("move_x", AxisSource::Negative(Binding::Key(Scancode::from_ll(sdl::SDL_Scancode::SDL_SCANCODE_A))));
+        map.bind_axis("move_x", AxisSource::Positive(Binding::Mouse(MouseButton::X2)));
+        map.bind_axis("look_x", AxisSource::MouseX(0.01));
+        map.set_dead_zone("look_x", 0.25);
+
+        let config = map.to_config();
+        let loaded = InputMap::from_config(&config).unwrap();
+
+        assert_eq!(loaded.bindings("jump"), &bindings[..]);
+        assert_eq!(loaded.to_config(), config);
+    }
+
+    #[test]
+    fn config_rejects_unknown_keys() {
+        assert!(InputMap::from_config("action jump key #1000").is_err());
+        assert!(InputMap::from_config("action jump key No Such Key").is_err());
+        assert!(InputMap::from_config("action jump mouse Side").is_err());
+    }
+
+    #[test]
+    fn controller_bindings_in_the_config() {
+        let config = "action jump button A\naction jump button DPadLeft\naxis move_x controller LeftX\naxis move_x controller TriggerRight\n";
+        let map = InputMap::from_config(config).unwrap();
+
+        assert_eq!(map.bindings("jump"), [Binding::ControllerButton(controller::Button::A), Binding::ControllerButton(controller::Button::DPadLeft)]);
+        assert_eq!(map.to_config(), config);
+        assert!(InputMap::from_config("action jump button Z").is_err());
+        assert!(InputMap::from_config("axis move_x controller Up").is_err());
+    }
+
+    #[test]
+    fn update_tracks_pressed_held_and_released() {
+        let space = Binding::Key(Scancode::from_ll(sdl::SDL_Scancode::SDL_SCANCODE_SPACE));
+        let a = Binding::ControllerButton(controller::Button::A);
+        let mut map = InputMap::new();
+        map.bind("jump", space);
+        map.bind("jump", a);
+
+        let frames: [&[Binding]; 7] = [&[], &[space], &[space], &[space, a], &[a], &[], &[]];
+        let mut states = Vec::new();
+        for down in frames {
+            map.sample(|binding| down.contains(&binding), |_| 0.0, (0, 0));
+            states.push((map.pressed("jump"), map.held("jump"), map.released("jump")));
+        }
+
+        #[rustfmt::skip]
+        let expected = [
+            (false, false, false),
+            (true, true, false),
+            (false, true, false),
+            (false, true, false),
+            (false, true, false),
+            (false, false, true),
+            (false, false, false),
+        ];
+        assert_eq!(states, expected);
+        assert!(!map.held("fire"));
+    }
+
+    #[test]
+    fn dead_zone_rescales_the_rest_of_the_range() {
+        let left = Binding::Key(Scancode::from_ll(sdl::SDL_Scancode::SDL_SCANCODE_A));
+        let right = Binding::Key(Scancode::from_ll(sdl::SDL_Scancode::SDL_SCANCODE_D));
+        let mut map = InputMap::new();
+        map.bind_axis("move_x", AxisSource::ControllerAxis(controller::Axis::LeftX));
+        map.bind_axis("move_x", AxisSource::Negative(left));
+        map.bind_axis("move_x", AxisSource::Positive(right));
+        map.set_dead_zone("move_x", 0.25);
+
+        let mut axis = |stick: f32, down: &[Binding]| {
+            map.sample(|binding| down.contains(&binding), |_| stick, (0, 0));
+            map.axis("move_x")
+        };
+        // signum * (|v| - dead zone) / (1 - dead zone)
+        assert_eq!(axis(0.125, &[]), 0.0);
+        assert_eq!(axis(-0.25, &[]), 0.0);
+        assert_eq!(axis(0.625, &[]), 0.5);
+        assert_eq!(axis(-0.625, &[]), -0.5);
+        assert_eq!(axis(-1.0, &[]), -1.0);
+        // the sources add up and are clamped before the dead zone
+        assert_eq!(axis(0.0, &[left, right]), 0.0);
+        assert_eq!(axis(0.0, &[right]), 1.0);
+        assert_eq!(axis(0.5, &[right]), 1.0);
+        assert_eq!(axis(0.375, &[left]), -0.5);
+    }
+
+    #[test]
+    fn mouse_axes_follow_the_movement_since_the_last_update() {
+        let mut map = InputMap::new();
+        map.bind_axis("look_x", AxisSource::MouseX(0.125));
+        map.bind_axis("look_y", AxisSource::MouseY(-0.25));
+
+        map.sample(|_| false, |_| 0.0, (10, 10));
+        assert_eq!((map.axis("look_x"), map.axis("look_y")), (0.0, 0.0));
+        map.sample(|_| false, |_| 0.0, (14, 12));
+        assert_eq!((map.axis("look_x"), map.axis("look_y")), (0.5, -0.5));
+        map.sample(|_| false, |_| 0.0, (14, 12));
+        assert_eq!((map.axis("look_x"), map.axis("look_y")), (0.0, 0.0));
+    }
+}
@@ -0,0 +1,483 @@
+use std::marker::PhantomData;
+
+use anyhow;
+use sdl2_sys as sdl;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum JoystickType {
+    Unknown,
+    GameController,
+    Wheel,
+    ArcadeStick,
+    FlightStick,
+    DancePad,
+    Guitar,
+    DrumKit,
+    ArcadePad,
+    Throttle,
+}
+
+impl JoystickType {
+    pub fn from_ll(type_: sdl::SDL_JoystickType) -> JoystickType {
+        match type_ {
+            sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_UNKNOWN => JoystickType::Unknown,
+            sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_GAMECONTROLLER => JoystickType::GameController,
+            sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_WHEEL => JoystickType::Wheel,
+            sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_ARCADE_STICK => JoystickType::ArcadeStick,
+            sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_FLIGHT_STICK => JoystickType::FlightStick,
+            sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_DANCE_PAD => JoystickType::DancePad,
+            sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_GUITAR => JoystickType::Guitar,
+            sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_DRUM_KIT => JoystickType::DrumKit,
+            sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_ARCADE_PAD => JoystickType::ArcadePad,
+            sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_THROTTLE => JoystickType::Throttle,
+        }
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn to_ll(self) -> sdl::SDL_JoystickType {
+        match self {
+            JoystickType::Unknown => sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_UNKNOWN,
+            JoystickType::GameController => sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_GAMECONTROLLER,
+            JoystickType::Wheel => sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_WHEEL,
+            JoystickType::ArcadeStick => sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_ARCADE_STICK,
+            JoystickType::FlightStick => sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_FLIGHT_STICK,
+            JoystickType::DancePad => sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_DANCE_PAD,
+            JoystickType::Guitar => sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_GUITAR,
+            JoystickType::DrumKit => sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_DRUM_KIT,
+            JoystickType::ArcadePad => sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_ARCADE_PAD,
+            JoystickType::Throttle => sdl::SDL_JoystickType::SDL_JOYSTICK_TYPE_THROTTLE,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PowerLevel {
+    Unknown,
+    Empty,
+    Low,
+    Medium,
+    Full,
+    Wired,
+}
+
+impl PowerLevel {
+    pub fn from_ll(level: sdl::SDL_JoystickPowerLevel) -> PowerLevel {
+        match level {
+            sdl::SDL_JoystickPowerLevel::SDL_JOYSTICK_POWER_EMPTY => PowerLevel::Empty,
+            sdl::SDL_JoystickPowerLevel::SDL_JOYSTICK_POWER_LOW => PowerLevel::Low,
+            sdl::SDL_JoystickPowerLevel::SDL_JOYSTICK_POWER_MEDIUM => PowerLevel::Medium,
+            sdl::SDL_JoystickPowerLevel::SDL_JOYSTICK_POWER_FULL => PowerLevel::Full,
+            sdl::SDL_JoystickPowerLevel::SDL_JOYSTICK_POWER_WIRED => PowerLevel::Wired,
+            _ => PowerLevel::Unknown,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum HatState {
+    Centered,
+    Up,
+    Right,
+    Down,
+    Left,
+    RightUp,
+    RightDown,
+    LeftUp,
+    LeftDown,
+}
+
+impl HatState {
+    pub fn from_ll(state: u8) -> HatState {
+        match state as u32 {
+            sdl::SDL_HAT_UP => HatState::Up,
+            sdl::SDL_HAT_RIGHT => HatState::Right,
+            sdl::SDL_HAT_DOWN => HatState::Down,
+            sdl::SDL_HAT_LEFT => HatState::Left,
+            sdl::SDL_HAT_RIGHTUP => HatState::RightUp,
+            sdl::SDL_HAT_RIGHTDOWN => HatState::RightDown,
+            sdl::SDL_HAT_LEFTUP => HatState::LeftUp,
+            sdl::SDL_HAT_LEFTDOWN => HatState::LeftDown,
+            _ => HatState::Centered,
+        }
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn to_ll(self) -> u8 {
+        let state = match self {
+            HatState::Centered => sdl::SDL_HAT_CENTERED,
+            HatState::Up => sdl::SDL_HAT_UP,
+            HatState::Right => sdl::SDL_HAT_RIGHT,
+            HatState::Down => sdl::SDL_HAT_DOWN,
+            HatState::Left => sdl::SDL_HAT_LEFT,
+            HatState::RightUp => sdl::SDL_HAT_RIGHTUP,
+            HatState::RightDown => sdl::SDL_HAT_RIGHTDOWN,
+            HatState::LeftUp => sdl::SDL_HAT_LEFTUP,
+            HatState::LeftDown => sdl::SDL_HAT_LEFTDOWN,
+        };
+
+        return state as u8;
+    }
+}
+
+/// Identifies a joystick model, stable across runs and machines.
+#[derive(Copy, Clone)]
+pub struct Guid {
+    pub raw: sdl::SDL_JoystickGUID,
+}
+
+impl PartialEq for Guid {
+    fn eq(&self, other: &Guid) -> bool {
+        self.raw.data == other.raw.data
+    }
+}
+
+impl Eq for Guid {}
+
+impl std::hash::Hash for Guid {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.raw.data.hash(state);
+    }
+}
+
+impl Guid {
+    /// Parses the 32 hex digit form used by `gamecontrollerdb.txt`.
+    #[doc(alias = "SDL_JoystickGetGUIDFromString")]
+    pub fn from_string(guid: &str) -> anyhow::Result<Guid> {
+        let guid = std::ffi::CString::new(guid)?;
+        let raw = unsafe { sdl::SDL_JoystickGetGUIDFromString(guid.as_ptr()) };
+        return Ok(Guid { raw });
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_zero(&self) -> bool {
+        self.raw.data.iter().all(|b| *b == 0)
+    }
+}
+
+impl std::fmt::Display for Guid {
+    #[doc(alias = "SDL_JoystickGetGUIDString")]
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let mut buf = [0 as std::ffi::c_char; 33];
+        let text = unsafe {
+            sdl::SDL_JoystickGetGUIDString(self.raw, buf.as_mut_ptr(), buf.len() as i32);
+            std::ffi::CStr::from_ptr(buf.as_ptr())
+        };
+        return write!(fmt, "{}", text.to_string_lossy());
+    }
+}
+
+impl std::fmt::Debug for Guid {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        return write!(fmt, "Guid({})", self);
+    }
+}
+
+/// Initializes the joystick subsystem.
+pub struct JoystickSubsystem;
+
+impl Drop for JoystickSubsystem {
+    fn drop(&mut self) {
+        unsafe {
+            sdl::SDL_QuitSubSystem(sdl::SDL_INIT_JOYSTICK);
+        }
+    }
+}
+
+impl JoystickSubsystem {
+    pub fn new() -> anyhow::Result<JoystickSubsystem> {
+        let res = unsafe { sdl::SDL_InitSubSystem(sdl::SDL_INIT_JOYSTICK) };
+
+        if res != 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(JoystickSubsystem {});
+    }
+
+    #[doc(alias = "SDL_NumJoysticks")]
+    pub fn num_joysticks(&self) -> anyhow::Result<u32> {
+        let count = unsafe { sdl::SDL_NumJoysticks() };
+
+        if count < 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(count as u32);
+    }
+
+    #[doc(alias = "SDL_JoystickNameForIndex")]
+    pub fn name_for_index(&self, device_index: u32) -> anyhow::Result<String> {
+        let name = unsafe { sdl::SDL_JoystickNameForIndex(device_index as i32) };
+
+        if name.is_null() {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(unsafe { std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned() });
+    }
+
+    /// GUID of the joystick at `device_index` without opening it, zero for an invalid index.
+    #[doc(alias = "SDL_JoystickGetDeviceGUID")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn guid_for_index(&self, device_index: u32) -> Guid {
+        Guid { raw: unsafe { sdl::SDL_JoystickGetDeviceGUID(device_index as i32) } }
+    }
+
+    /// Opens the joystick at `device_index`.
+    ///
+    /// Opening a device twice, or opening a device that is also open as a `GameController`,
+    /// gives another handle to the same joystick.
+    #[doc(alias = "SDL_JoystickOpen")]
+    pub fn open(&self, device_index: u32) -> anyhow::Result<Joystick<'_>> {
+        let raw = unsafe { sdl::SDL_JoystickOpen(device_index as i32) };
+
+        if raw.is_null() {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(Joystick { raw, _marker: PhantomData });
+    }
+
+    /// Attaches a virtual joystick and returns its device index, e.g. to test input code
+    /// without hardware. A `JoystickType::GameController` joystick with 6 axes and 15 buttons
+    /// also opens as a `GameController`, its buttons and axes follow the `controller::Button` and `controller::Axis` order.
+    ///
+    /// Its state is driven with the `set_virtual_*` methods of the opened `Joystick`.
+    #[doc(alias = "SDL_JoystickAttachVirtual")]
+    pub fn attach_virtual(&self, type_: JoystickType, naxes: u32, nbuttons: u32, nhats: u32) -> anyhow::Result<u32> {
+        let device_index = unsafe { sdl::SDL_JoystickAttachVirtual(type_.to_ll(), naxes as i32, nbuttons as i32, nhats as i32) };
+
+        if device_index < 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(device_index as u32);
+    }
+
+    #[doc(alias = "SDL_JoystickDetachVirtual")]
+    pub fn detach_virtual(&self, device_index: u32) -> anyhow::Result<()> {
+        unsafe {
+            if sdl::SDL_JoystickDetachVirtual(device_index as i32) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+
+    #[doc(alias = "SDL_JoystickIsVirtual")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_virtual(&self, device_index: u32) -> bool {
+        unsafe { sdl::SDL_JoystickIsVirtual(device_index as i32) == sdl::SDL_bool::SDL_TRUE }
+    }
+
+    /// Polls the joysticks, only needed if joystick events are disabled.
+    #[doc(alias = "SDL_JoystickUpdate")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn update(&self) {
+        unsafe { sdl::SDL_JoystickUpdate() }
+    }
+}
+
+/// An open joystick, virtual ones included, closed on drop. Borrows the subsystem so it can't outlive it.
+pub struct Joystick<'a> {
+    raw: *mut sdl::SDL_Joystick,
+    _marker: PhantomData<&'a JoystickSubsystem>,
+}
+
+impl<'a> Drop for Joystick<'a> {
+    #[doc(alias = "SDL_JoystickClose")]
+    fn drop(&mut self) {
+        unsafe {
+            sdl::SDL_JoystickClose(self.raw);
+        }
+    }
+}
+
+impl<'a> Joystick<'a> {
+    #[doc(alias = "SDL_JoystickName")]
+    pub fn name(&self) -> String {
+        unsafe {
+            let name = sdl::SDL_JoystickName(self.raw);
+            if name.is_null() {
+                return String::new();
+            }
+            return std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned();
+        }
+    }
+
+    #[doc(alias = "SDL_JoystickGetGUID")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn guid(&self) -> Guid {
+        Guid { raw: unsafe { sdl::SDL_JoystickGetGUID(self.raw) } }
+    }
+
+    /// The id used as `which` by joystick and controller events.
+    #[doc(alias = "SDL_JoystickInstanceID")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn instance_id(&self) -> i32 {
+        unsafe { sdl::SDL_JoystickInstanceID(self.raw) }
+    }
+
+    #[doc(alias = "SDL_JoystickGetType")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn joystick_type(&self) -> JoystickType {
+        JoystickType::from_ll(unsafe { sdl::SDL_JoystickGetType(self.raw) })
+    }
+
+    #[doc(alias = "SDL_JoystickCurrentPowerLevel")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn power_level(&self) -> PowerLevel {
+        PowerLevel::from_ll(unsafe { sdl::SDL_JoystickCurrentPowerLevel(self.raw) })
+    }
+
+    /// False once the joystick was unplugged.
+    #[doc(alias = "SDL_JoystickGetAttached")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn attached(&self) -> bool {
+        unsafe { sdl::SDL_JoystickGetAttached(self.raw) == sdl::SDL_bool::SDL_TRUE }
+    }
+
+    #[doc(alias = "SDL_JoystickNumAxes")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn num_axes(&self) -> u32 {
+        unsafe { sdl::SDL_JoystickNumAxes(self.raw).max(0) as u32 }
+    }
+
+    #[doc(alias = "SDL_JoystickNumButtons")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn num_buttons(&self) -> u32 {
+        unsafe { sdl::SDL_JoystickNumButtons(self.raw).max(0) as u32 }
+    }
+
+    #[doc(alias = "SDL_JoystickNumHats")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn num_hats(&self) -> u32 {
+        unsafe { sdl::SDL_JoystickNumHats(self.raw).max(0) as u32 }
+    }
+
+    #[doc(alias = "SDL_JoystickNumBalls")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn num_balls(&self) -> u32 {
+        unsafe { sdl::SDL_JoystickNumBalls(self.raw).max(0) as u32 }
+    }
+
+    /// Axis position from -32768 to 32767, 0 for an out of range `axis`.
+    #[doc(alias = "SDL_JoystickGetAxis")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn axis(&self, axis: u32) -> i16 {
+        unsafe { sdl::SDL_JoystickGetAxis(self.raw, axis as i32) }
+    }
+
+    #[doc(alias = "SDL_JoystickGetButton")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn button(&self, button: u32) -> bool {
+        unsafe { sdl::SDL_JoystickGetButton(self.raw, button as i32) != 0 }
+    }
+
+    #[doc(alias = "SDL_JoystickGetHat")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn hat(&self, hat: u32) -> HatState {
+        HatState::from_ll(unsafe { sdl::SDL_JoystickGetHat(self.raw, hat as i32) })
+    }
+
+    /// Motion of the trackball since the last call, as `(dx, dy)`.
+    #[doc(alias = "SDL_JoystickGetBall")]
+    pub fn ball(&self, ball: u32) -> anyhow::Result<(i32, i32)> {
+        let mut dx = 0;
+        let mut dy = 0;
+        unsafe {
+            if sdl::SDL_JoystickGetBall(self.raw, ball as i32, &mut dx, &mut dy) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok((dx, dy));
+    }
+
+    #[doc(alias = "SDL_JoystickRumble")]
+    pub fn set_rumble(&mut self, low_frequency: u16, high_frequency: u16, duration_ms: u32) -> anyhow::Result<()> {
+        unsafe {
+            if sdl::SDL_JoystickRumble(self.raw, low_frequency, high_frequency, duration_ms) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Sets an axis of a virtual joystick, the change is seen after the next event poll.
+    #[doc(alias = "SDL_JoystickSetVirtualAxis")]
+    pub fn set_virtual_axis(&mut self, axis: u32, value: i16) -> anyhow::Result<()> {
+        unsafe {
+            if sdl::SDL_JoystickSetVirtualAxis(self.raw, axis as i32, value) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+
+    #[doc(alias = "SDL_JoystickSetVirtualButton")]
+    pub fn set_virtual_button(&mut self, button: u32, pressed: bool) -> anyhow::Result<()> {
+        let state = if pressed { sdl::SDL_PRESSED } else { sdl::SDL_RELEASED };
+        unsafe {
+            if sdl::SDL_JoystickSetVirtualButton(self.raw, button as i32, state as u8) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+
+    #[doc(alias = "SDL_JoystickSetVirtualHat")]
+    pub fn set_virtual_hat(&mut self, hat: u32, state: HatState) -> anyhow::Result<()> {
+        unsafe {
+            if sdl::SDL_JoystickSetVirtualHat(self.raw, hat as i32, state.to_ll()) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_joystick_round_trip() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        let joysticks = JoystickSubsystem::new().unwrap();
+        let before = joysticks.num_joysticks().unwrap();
+
+        let index = joysticks.attach_virtual(JoystickType::ArcadeStick, 2, 3, 1).unwrap();
+        assert_eq!(joysticks.num_joysticks().unwrap(), before + 1);
+        assert!(joysticks.is_virtual(index));
+        assert!(!joysticks.is_virtual(index + 1));
+
+        let mut joystick = joysticks.open(index).unwrap();
+        assert_eq!((joystick.num_axes(), joystick.num_buttons(), joystick.num_hats()), (2, 3, 1));
+        assert_eq!(joystick.joystick_type(), JoystickType::ArcadeStick);
+        assert!(!joystick.guid().is_zero());
+        assert_eq!(joystick.guid(), joysticks.guid_for_index(index));
+        assert!(joystick.instance_id() >= 0);
+        assert_eq!(joysticks.open(index).unwrap().instance_id(), joystick.instance_id());
+
+        joystick.set_virtual_axis(1, -1234).unwrap();
+        joystick.set_virtual_button(2, true).unwrap();
+        joystick.set_virtual_hat(0, HatState::LeftUp).unwrap();
+        assert!(joystick.set_virtual_button(3, true).is_err());
+        // the virtual state is applied on the next update
+        assert!(!joystick.button(2));
+        joysticks.update();
+        assert_eq!((joystick.axis(0), joystick.axis(1)), (0, -1234));
+        assert_eq!((joystick.button(0), joystick.button(2)), (false, true));
+        assert_eq!(joystick.hat(0), HatState::LeftUp);
+
+        joysticks.detach_virtual(index).unwrap();
+        assert_eq!(joysticks.num_joysticks().unwrap(), before);
+        assert!(!joystick.attached());
+    }
+}
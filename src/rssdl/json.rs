@@ -0,0 +1,391 @@
+//! Just enough JSON to read sheet exports, objects keep their key order.
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// The member `key` of an object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        self.as_f64().map(|n| n as i32)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+}
+
+/// Arrays and objects nested deeper than this are rejected instead of overflowing the stack.
+const MAX_DEPTH: usize = 128;
+
+pub fn parse(text: &str) -> anyhow::Result<Value> {
+    let mut parser = Parser { bytes: text.as_bytes(), pos: 0, depth: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != parser.bytes.len() {
+        return Err(parser.error("trailing characters"));
+    }
+
+    return Ok(value);
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, what: &str) -> anyhow::Error {
+        anyhow::anyhow!("JSON: {} at byte {} {}({},{})", what, self.pos, file!(), line!(), column!())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.bytes.len() && matches!(self.bytes[self.pos], b' ' | b'\t' | b'\n' | b'\r') {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.bytes.get(self.pos).copied()
+    }
+
+    /// Skips `byte` if it is next, without skipping whitespace first.
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.bytes.get(self.pos) == Some(&byte) {
+            self.pos += 1;
+            return true;
+        }
+
+        return false;
+    }
+
+    fn expect(&mut self, byte: u8) -> anyhow::Result<()> {
+        if self.peek() != Some(byte) {
+            return Err(self.error(&format!("expected '{}'", byte as char)));
+        }
+        self.pos += 1;
+
+        return Ok(());
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> anyhow::Result<Value> {
+        if !self.bytes[self.pos..].starts_with(word.as_bytes()) {
+            return Err(self.error("unexpected character"));
+        }
+        self.pos += word.len();
+
+        return Ok(value);
+    }
+
+    fn value(&mut self) -> anyhow::Result<Value> {
+        match self.peek() {
+            Some(b'{') => self.nested(Parser::object),
+            Some(b'[') => self.nested(Parser::array),
+            Some(b'"') => Ok(Value::String(self.string()?)),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end")),
+        }
+    }
+
+    fn nested(&mut self, parse: fn(&mut Parser<'a>) -> anyhow::Result<Value>) -> anyhow::Result<Value> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+
+        return value;
+    }
+
+    fn object(&mut self) -> anyhow::Result<Value> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(members));
+        }
+
+        loop {
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected a key"));
+            }
+            let key = self.string()?;
+            self.expect(b':')?;
+            members.push((key, self.value()?));
+
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(members));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> anyhow::Result<Value> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Value::Array(items));
+        }
+
+        loop {
+            items.push(self.value()?);
+
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    /// Skips a run of digits, returns how many there were.
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_digit() {
+            self.pos += 1;
+        }
+
+        return self.pos - start;
+    }
+
+    fn number(&mut self) -> anyhow::Result<Value> {
+        let start = self.pos;
+        self.eat(b'-');
+        // no leading zeros, "01" stops after the 0
+        if !self.eat(b'0') && self.digits() == 0 {
+            return Err(self.error("invalid number"));
+        }
+        if self.eat(b'.') && self.digits() == 0 {
+            return Err(self.error("invalid number"));
+        }
+        if self.eat(b'e') || self.eat(b'E') {
+            if !self.eat(b'+') {
+                self.eat(b'-');
+            }
+            if self.digits() == 0 {
+                return Err(self.error("invalid number"));
+            }
+        }
+
+        // the bytes are all ascii
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap();
+        match text.parse::<f64>() {
+            Ok(n) => Ok(Value::Number(n)),
+            Err(_) => Err(self.error("invalid number")),
+        }
+    }
+
+    fn hex4(&mut self) -> anyhow::Result<u32> {
+        let digits = self.bytes.get(self.pos..self.pos + 4).and_then(|d| std::str::from_utf8(d).ok());
+        match digits.and_then(|d| u32::from_str_radix(d, 16).ok()) {
+            Some(code) => {
+                self.pos += 4;
+                Ok(code)
+            }
+            None => Err(self.error("invalid \\u escape")),
+        }
+    }
+
+    fn string(&mut self) -> anyhow::Result<String> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+
+        loop {
+            let byte = match self.bytes.get(self.pos) {
+                Some(byte) => *byte,
+                None => return Err(self.error("unterminated string")),
+            };
+            self.pos += 1;
+
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escape = match self.bytes.get(self.pos) {
+                        Some(escape) => *escape,
+                        None => return Err(self.error("unterminated string")),
+                    };
+                    self.pos += 1;
+
+                    let c = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.hex4()?;
+                            if (0xd800..0xdc00).contains(&code) && self.bytes[self.pos..].starts_with(b"\\u") {
+                                let high_end = self.pos;
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                if (0xdc00..0xe000).contains(&low) {
+                                    code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                                } else {
+                                    // not a pair, the second escape is read on its own
+                                    self.pos = high_end;
+                                }
+                            }
+                            // lone surrogates aren't chars
+                            char::from_u32(code).unwrap_or('\u{fffd}')
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    let mut utf8 = [0; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut utf8).as_bytes());
+                }
+                byte => bytes.push(byte),
+            }
+        }
+
+        match String::from_utf8(bytes) {
+            Ok(s) => Ok(s),
+            Err(_) => Err(self.error("invalid utf-8 in string")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(json: &str) -> String {
+        parse(json).unwrap().as_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn values_and_key_order() {
+        let value = parse(r#" {"b": [1, true, null], "a": {"x": "y"}, "c": false} "#).unwrap();
+
+        let keys: Vec<&str> = value.as_object().unwrap().iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["b", "a", "c"]);
+        assert_eq!(value.get("b").unwrap().as_array().unwrap(), &[Value::Number(1.0), Value::Bool(true), Value::Null]);
+        assert_eq!(value.get("a").and_then(|a| a.get("x")).and_then(|x| x.as_str()), Some("y"));
+        assert_eq!(value.get("c").and_then(|c| c.as_bool()), Some(false));
+        assert_eq!(parse("[]").unwrap(), Value::Array(Vec::new()));
+        assert_eq!(parse("{}").unwrap(), Value::Object(Vec::new()));
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(string(r#""a\"b\\c\/d""#), "a\"b\\c/d");
+        assert_eq!(string(r#""\b\f\n\r\t""#), "\u{8}\u{c}\n\r\t");
+        assert_eq!(string(r#""\u0041\u00e9\u20AC""#), "Aé€");
+        assert_eq!(string("\"héllo\""), "héllo");
+
+        assert!(parse(r#""\x""#).is_err());
+        assert!(parse(r#""\u12""#).is_err());
+        assert!(parse(r#""\u12g4""#).is_err());
+        assert!(parse(r#""abc"#).is_err());
+        assert!(parse(r#""abc\"#).is_err());
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        assert_eq!(string(r#""\ud83d\ude00""#), "😀");
+        assert_eq!(string(r#""\uD834\uDD1E""#), "𝄞");
+
+        // lone halves become the replacement character, without eating what follows
+        assert_eq!(string(r#""\ud83d""#), "\u{fffd}");
+        assert_eq!(string(r#""\ud83dx""#), "\u{fffd}x");
+        assert_eq!(string(r#""\ud83d\u0041""#), "\u{fffd}A");
+        assert_eq!(string(r#""\ude00""#), "\u{fffd}");
+    }
+
+    #[test]
+    fn numbers() {
+        let numbers = [("0", 0.0), ("-0", -0.0), ("42", 42.0), ("-7", -7.0), ("3.25", 3.25), ("1e3", 1000.0), ("2.5E-2", 0.025), ("-1e+2", -100.0)];
+        for (json, number) in numbers {
+            assert_eq!(parse(json).unwrap().as_f64(), Some(number), "{}", json);
+        }
+        assert_eq!(parse("-12.9").unwrap().as_i32(), Some(-12));
+
+        for json in ["-", "01", "1.", ".5", "-.5", "1e", "1e+", "+1", "1.2.3", "--1", "0x10"] {
+            assert!(parse(json).is_err(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn trailing_garbage() {
+        assert!(parse("{} x").is_err());
+        assert!(parse("1 2").is_err());
+        assert!(parse("[1]]").is_err());
+        assert!(parse("truex").is_err());
+        assert!(parse("[1,]").is_err());
+        assert!(parse(r#"{"a": 1,}"#).is_err());
+        assert!(parse("").is_err());
+        assert!(parse(" [1] \n").is_ok());
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested = |depth: usize| "[".repeat(depth) + &"]".repeat(depth);
+
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert!(parse(&nested(MAX_DEPTH + 1)).unwrap_err().to_string().contains("nesting too deep"));
+        // fails fast instead of overflowing the stack
+        assert!(parse(&"{\"a\":".repeat(100_000)).is_err());
+        assert!(parse(&"[".repeat(1_000_000)).is_err());
+    }
+}
@@ -0,0 +1,712 @@
+use std::ffi::CString;
+
+use sdl2_sys as sdl;
+
+use crate::core::Rect;
+
+/// Physical key, independent of the keyboard layout.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Scancode {
+    pub raw: sdl::SDL_Scancode,
+}
+
+impl Scancode {
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn from_ll(raw: sdl::SDL_Scancode) -> Scancode {
+        Scancode { raw }
+    }
+
+    /// The scancode with the numeric value `code`, `None` if SDL has no such scancode.
+    pub fn from_i32(code: i32) -> Option<Scancode> {
+        use sdl::SDL_Scancode as S;
+
+        let valid = code == S::SDL_SCANCODE_UNKNOWN as i32
+            || (S::SDL_SCANCODE_A as i32..=S::SDL_SCANCODE_VOLUMEDOWN as i32).contains(&code)
+            || (S::SDL_SCANCODE_KP_COMMA as i32..=S::SDL_SCANCODE_EXSEL as i32).contains(&code)
+            || (S::SDL_SCANCODE_KP_00 as i32..=S::SDL_SCANCODE_KP_HEXADECIMAL as i32).contains(&code)
+            || (S::SDL_SCANCODE_LCTRL as i32..=S::SDL_SCANCODE_RGUI as i32).contains(&code)
+            || (S::SDL_SCANCODE_MODE as i32..=S::SDL_SCANCODE_ENDCALL as i32).contains(&code);
+        if !valid {
+            return None;
+        }
+
+        // every value in the ranges above is a variant of the enum
+        return Some(Scancode { raw: unsafe { std::mem::transmute::<i32, sdl::SDL_Scancode>(code) } });
+    }
+
+    /// Index into the `SDL_GetKeyboardState` array.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn index(self) -> usize {
+        self.raw as usize
+    }
+
+    /// Looks up a scancode by its human readable name, e.g. `"Left Shift"`.
+    #[doc(alias = "SDL_GetScancodeFromName")]
+    pub fn from_name(name: &str) -> Option<Scancode> {
+        let name = CString::new(name).ok()?;
+        let raw = unsafe { sdl::SDL_GetScancodeFromName(name.as_ptr()) };
+        if raw == sdl::SDL_Scancode::SDL_SCANCODE_UNKNOWN {
+            return None;
+        }
+
+        return Some(Scancode { raw });
+    }
+
+    /// The human readable name, empty if the scancode has none.
+    #[doc(alias = "SDL_GetScancodeName")]
+    pub fn name(self) -> String {
+        unsafe { std::ffi::CStr::from_ptr(sdl::SDL_GetScancodeName(self.raw)).to_string_lossy().into_owned() }
+    }
+
+    /// The key this scancode produces with the current keyboard layout.
+    #[doc(alias = "SDL_GetKeyFromScancode")]
+    pub fn to_keycode(self) -> Keycode {
+        Keycode { raw: unsafe { sdl::SDL_GetKeyFromScancode(self.raw) } }
+    }
+}
+
+impl From<sdl::SDL_Scancode> for Scancode {
+    fn from(raw: sdl::SDL_Scancode) -> Scancode {
+        Scancode { raw }
+    }
+}
+
+impl std::fmt::Debug for Scancode {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        return write!(fmt, "Scancode({:?})", self.name());
+    }
+}
+
+/// Virtual key, the symbol a key produces with the current keyboard layout.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Keycode {
+    pub raw: sdl::SDL_Keycode,
+}
+
+impl Keycode {
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn from_ll(raw: sdl::SDL_Keycode) -> Keycode {
+        Keycode { raw }
+    }
+
+    /// Looks up a key by its human readable name, e.g. `"Space"` or `"A"`.
+    #[doc(alias = "SDL_GetKeyFromName")]
+    pub fn from_name(name: &str) -> Option<Keycode> {
+        let name = CString::new(name).ok()?;
+        let raw = unsafe { sdl::SDL_GetKeyFromName(name.as_ptr()) };
+        if raw == sdl::SDL_KeyCode::SDLK_UNKNOWN as i32 {
+            return None;
+        }
+
+        return Some(Keycode { raw });
+    }
+
+    /// The human readable name, empty if the key has none.
+    #[doc(alias = "SDL_GetKeyName")]
+    pub fn name(self) -> String {
+        unsafe { std::ffi::CStr::from_ptr(sdl::SDL_GetKeyName(self.raw)).to_string_lossy().into_owned() }
+    }
+
+    /// The physical key producing this key with the current keyboard layout.
+    #[doc(alias = "SDL_GetScancodeFromKey")]
+    pub fn to_scancode(self) -> Scancode {
+        Scancode { raw: unsafe { sdl::SDL_GetScancodeFromKey(self.raw) } }
+    }
+}
+
+impl std::fmt::Debug for Keycode {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        return write!(fmt, "Keycode({:?})", self.name());
+    }
+}
+
+/// Key modifier bitflags, as found in key events.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Default, Debug)]
+pub struct Mod {
+    pub raw: u16,
+}
+
+impl Mod {
+    pub const NONE: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_NONE as u16 };
+    pub const LSHIFT: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_LSHIFT as u16 };
+    pub const RSHIFT: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_RSHIFT as u16 };
+    pub const LCTRL: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_LCTRL as u16 };
+    pub const RCTRL: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_RCTRL as u16 };
+    pub const LALT: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_LALT as u16 };
+    pub const RALT: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_RALT as u16 };
+    pub const LGUI: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_LGUI as u16 };
+    pub const RGUI: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_RGUI as u16 };
+    pub const NUM: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_NUM as u16 };
+    pub const CAPS: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_CAPS as u16 };
+    pub const MODE: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_MODE as u16 };
+    pub const SCROLL: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_SCROLL as u16 };
+    pub const CTRL: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_CTRL as u16 };
+    pub const SHIFT: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_SHIFT as u16 };
+    pub const ALT: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_ALT as u16 };
+    pub const GUI: Mod = Mod { raw: sdl::SDL_Keymod::KMOD_GUI as u16 };
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn from_ll(raw: u16) -> Mod {
+        Mod { raw }
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn is_empty(self) -> bool {
+        self.raw == 0
+    }
+
+    /// True if every flag of `other` is set.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn contains(self, other: Mod) -> bool {
+        self.raw & other.raw == other.raw
+    }
+
+    /// True if any flag of `other` is set, e.g. `keymod.intersects(Mod::CTRL)` for either ctrl key.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn intersects(self, other: Mod) -> bool {
+        self.raw & other.raw != 0
+    }
+}
+
+impl std::ops::BitOr for Mod {
+    type Output = Mod;
+
+    fn bitor(self, rhs: Mod) -> Mod {
+        Mod { raw: self.raw | rhs.raw }
+    }
+}
+
+impl std::ops::BitOrAssign for Mod {
+    fn bitor_assign(&mut self, rhs: Mod) {
+        self.raw |= rhs.raw;
+    }
+}
+
+impl std::ops::BitAnd for Mod {
+    type Output = Mod;
+
+    fn bitand(self, rhs: Mod) -> Mod {
+        Mod { raw: self.raw & rhs.raw }
+    }
+}
+
+impl std::ops::Not for Mod {
+    type Output = Mod;
+
+    fn not(self) -> Mod {
+        Mod { raw: !self.raw }
+    }
+}
+
+/// Starts delivering `Event::TextInput` and, while an IME composes, `Event::TextEditing`.
+///
+/// On platforms with an on-screen keyboard this shows it.
+#[doc(alias = "SDL_StartTextInput")]
+pub fn start_text_input() {
+    unsafe { sdl::SDL_StartTextInput() };
+}
+
+#[doc(alias = "SDL_StopTextInput")]
+pub fn stop_text_input() {
+    unsafe { sdl::SDL_StopTextInput() };
+}
+
+#[doc(alias = "SDL_IsTextInputActive")]
+pub fn is_text_input_active() -> bool {
+    unsafe { sdl::SDL_IsTextInputActive() == sdl::SDL_bool::SDL_TRUE }
+}
+
+/// Tells the IME where the text field is, so the candidate list is shown next to it.
+#[doc(alias = "SDL_SetTextInputRect")]
+pub fn set_text_input_rect(rect: &Rect) {
+    unsafe { sdl::SDL_SetTextInputRect(&rect.raw) };
+}
+
+impl Scancode {
+    pub const UNKNOWN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_UNKNOWN };
+    pub const A: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_A };
+    pub const B: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_B };
+    pub const C: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_C };
+    pub const D: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_D };
+    pub const E: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_E };
+    pub const F: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F };
+    pub const G: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_G };
+    pub const H: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_H };
+    pub const I: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_I };
+    pub const J: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_J };
+    pub const K: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_K };
+    pub const L: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_L };
+    pub const M: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_M };
+    pub const N: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_N };
+    pub const O: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_O };
+    pub const P: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_P };
+    pub const Q: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_Q };
+    pub const R: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_R };
+    pub const S: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_S };
+    pub const T: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_T };
+    pub const U: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_U };
+    pub const V: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_V };
+    pub const W: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_W };
+    pub const X: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_X };
+    pub const Y: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_Y };
+    pub const Z: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_Z };
+    pub const NUM_1: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_1 };
+    pub const NUM_2: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_2 };
+    pub const NUM_3: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_3 };
+    pub const NUM_4: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_4 };
+    pub const NUM_5: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_5 };
+    pub const NUM_6: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_6 };
+    pub const NUM_7: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_7 };
+    pub const NUM_8: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_8 };
+    pub const NUM_9: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_9 };
+    pub const NUM_0: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_0 };
+    pub const RETURN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_RETURN };
+    pub const ESCAPE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_ESCAPE };
+    pub const BACKSPACE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_BACKSPACE };
+    pub const TAB: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_TAB };
+    pub const SPACE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_SPACE };
+    pub const MINUS: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_MINUS };
+    pub const EQUALS: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_EQUALS };
+    pub const LEFTBRACKET: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LEFTBRACKET };
+    pub const RIGHTBRACKET: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_RIGHTBRACKET };
+    pub const BACKSLASH: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_BACKSLASH };
+    pub const NONUSHASH: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_NONUSHASH };
+    pub const SEMICOLON: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_SEMICOLON };
+    pub const APOSTROPHE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_APOSTROPHE };
+    pub const GRAVE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_GRAVE };
+    pub const COMMA: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_COMMA };
+    pub const PERIOD: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_PERIOD };
+    pub const SLASH: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_SLASH };
+    pub const CAPSLOCK: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_CAPSLOCK };
+    pub const F1: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F1 };
+    pub const F2: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F2 };
+    pub const F3: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F3 };
+    pub const F4: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F4 };
+    pub const F5: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F5 };
+    pub const F6: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F6 };
+    pub const F7: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F7 };
+    pub const F8: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F8 };
+    pub const F9: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F9 };
+    pub const F10: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F10 };
+    pub const F11: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F11 };
+    pub const F12: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F12 };
+    pub const PRINTSCREEN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_PRINTSCREEN };
+    pub const SCROLLLOCK: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_SCROLLLOCK };
+    pub const PAUSE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_PAUSE };
+    pub const INSERT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_INSERT };
+    pub const HOME: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_HOME };
+    pub const PAGEUP: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_PAGEUP };
+    pub const DELETE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_DELETE };
+    pub const END: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_END };
+    pub const PAGEDOWN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_PAGEDOWN };
+    pub const RIGHT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_RIGHT };
+    pub const LEFT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LEFT };
+    pub const DOWN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_DOWN };
+    pub const UP: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_UP };
+    pub const NUMLOCKCLEAR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_NUMLOCKCLEAR };
+    pub const KP_DIVIDE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_DIVIDE };
+    pub const KP_MULTIPLY: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_MULTIPLY };
+    pub const KP_MINUS: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_MINUS };
+    pub const KP_PLUS: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_PLUS };
+    pub const KP_ENTER: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_ENTER };
+    pub const KP_1: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_1 };
+    pub const KP_2: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_2 };
+    pub const KP_3: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_3 };
+    pub const KP_4: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_4 };
+    pub const KP_5: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_5 };
+    pub const KP_6: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_6 };
+    pub const KP_7: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_7 };
+    pub const KP_8: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_8 };
+    pub const KP_9: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_9 };
+    pub const KP_0: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_0 };
+    pub const KP_PERIOD: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_PERIOD };
+    pub const NONUSBACKSLASH: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_NONUSBACKSLASH };
+    pub const APPLICATION: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_APPLICATION };
+    pub const POWER: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_POWER };
+    pub const KP_EQUALS: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_EQUALS };
+    pub const F13: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F13 };
+    pub const F14: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F14 };
+    pub const F15: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F15 };
+    pub const F16: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F16 };
+    pub const F17: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F17 };
+    pub const F18: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F18 };
+    pub const F19: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F19 };
+    pub const F20: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F20 };
+    pub const F21: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F21 };
+    pub const F22: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F22 };
+    pub const F23: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F23 };
+    pub const F24: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_F24 };
+    pub const EXECUTE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_EXECUTE };
+    pub const HELP: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_HELP };
+    pub const MENU: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_MENU };
+    pub const SELECT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_SELECT };
+    pub const STOP: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_STOP };
+    pub const AGAIN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AGAIN };
+    pub const UNDO: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_UNDO };
+    pub const CUT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_CUT };
+    pub const COPY: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_COPY };
+    pub const PASTE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_PASTE };
+    pub const FIND: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_FIND };
+    pub const MUTE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_MUTE };
+    pub const VOLUMEUP: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_VOLUMEUP };
+    pub const VOLUMEDOWN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_VOLUMEDOWN };
+    pub const KP_COMMA: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_COMMA };
+    pub const KP_EQUALSAS400: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_EQUALSAS400 };
+    pub const INTERNATIONAL1: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_INTERNATIONAL1 };
+    pub const INTERNATIONAL2: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_INTERNATIONAL2 };
+    pub const INTERNATIONAL3: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_INTERNATIONAL3 };
+    pub const INTERNATIONAL4: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_INTERNATIONAL4 };
+    pub const INTERNATIONAL5: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_INTERNATIONAL5 };
+    pub const INTERNATIONAL6: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_INTERNATIONAL6 };
+    pub const INTERNATIONAL7: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_INTERNATIONAL7 };
+    pub const INTERNATIONAL8: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_INTERNATIONAL8 };
+    pub const INTERNATIONAL9: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_INTERNATIONAL9 };
+    pub const LANG1: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LANG1 };
+    pub const LANG2: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LANG2 };
+    pub const LANG3: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LANG3 };
+    pub const LANG4: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LANG4 };
+    pub const LANG5: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LANG5 };
+    pub const LANG6: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LANG6 };
+    pub const LANG7: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LANG7 };
+    pub const LANG8: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LANG8 };
+    pub const LANG9: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LANG9 };
+    pub const ALTERASE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_ALTERASE };
+    pub const SYSREQ: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_SYSREQ };
+    pub const CANCEL: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_CANCEL };
+    pub const CLEAR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_CLEAR };
+    pub const PRIOR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_PRIOR };
+    pub const RETURN2: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_RETURN2 };
+    pub const SEPARATOR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_SEPARATOR };
+    pub const OUT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_OUT };
+    pub const OPER: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_OPER };
+    pub const CLEARAGAIN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_CLEARAGAIN };
+    pub const CRSEL: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_CRSEL };
+    pub const EXSEL: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_EXSEL };
+    pub const KP_00: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_00 };
+    pub const KP_000: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_000 };
+    pub const THOUSANDSSEPARATOR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_THOUSANDSSEPARATOR };
+    pub const DECIMALSEPARATOR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_DECIMALSEPARATOR };
+    pub const CURRENCYUNIT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_CURRENCYUNIT };
+    pub const CURRENCYSUBUNIT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_CURRENCYSUBUNIT };
+    pub const KP_LEFTPAREN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_LEFTPAREN };
+    pub const KP_RIGHTPAREN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_RIGHTPAREN };
+    pub const KP_LEFTBRACE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_LEFTBRACE };
+    pub const KP_RIGHTBRACE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_RIGHTBRACE };
+    pub const KP_TAB: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_TAB };
+    pub const KP_BACKSPACE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_BACKSPACE };
+    pub const KP_A: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_A };
+    pub const KP_B: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_B };
+    pub const KP_C: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_C };
+    pub const KP_D: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_D };
+    pub const KP_E: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_E };
+    pub const KP_F: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_F };
+    pub const KP_XOR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_XOR };
+    pub const KP_POWER: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_POWER };
+    pub const KP_PERCENT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_PERCENT };
+    pub const KP_LESS: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_LESS };
+    pub const KP_GREATER: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_GREATER };
+    pub const KP_AMPERSAND: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_AMPERSAND };
+    pub const KP_DBLAMPERSAND: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_DBLAMPERSAND };
+    pub const KP_VERTICALBAR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_VERTICALBAR };
+    pub const KP_DBLVERTICALBAR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_DBLVERTICALBAR };
+    pub const KP_COLON: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_COLON };
+    pub const KP_HASH: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_HASH };
+    pub const KP_SPACE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_SPACE };
+    pub const KP_AT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_AT };
+    pub const KP_EXCLAM: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_EXCLAM };
+    pub const KP_MEMSTORE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_MEMSTORE };
+    pub const KP_MEMRECALL: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_MEMRECALL };
+    pub const KP_MEMCLEAR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_MEMCLEAR };
+    pub const KP_MEMADD: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_MEMADD };
+    pub const KP_MEMSUBTRACT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_MEMSUBTRACT };
+    pub const KP_MEMMULTIPLY: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_MEMMULTIPLY };
+    pub const KP_MEMDIVIDE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_MEMDIVIDE };
+    pub const KP_PLUSMINUS: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_PLUSMINUS };
+    pub const KP_CLEAR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_CLEAR };
+    pub const KP_CLEARENTRY: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_CLEARENTRY };
+    pub const KP_BINARY: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_BINARY };
+    pub const KP_OCTAL: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_OCTAL };
+    pub const KP_DECIMAL: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_DECIMAL };
+    pub const KP_HEXADECIMAL: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KP_HEXADECIMAL };
+    pub const LCTRL: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LCTRL };
+    pub const LSHIFT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LSHIFT };
+    pub const LALT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LALT };
+    pub const LGUI: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_LGUI };
+    pub const RCTRL: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_RCTRL };
+    pub const RSHIFT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_RSHIFT };
+    pub const RALT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_RALT };
+    pub const RGUI: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_RGUI };
+    pub const MODE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_MODE };
+    pub const AUDIONEXT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AUDIONEXT };
+    pub const AUDIOPREV: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AUDIOPREV };
+    pub const AUDIOSTOP: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AUDIOSTOP };
+    pub const AUDIOPLAY: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AUDIOPLAY };
+    pub const AUDIOMUTE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AUDIOMUTE };
+    pub const MEDIASELECT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_MEDIASELECT };
+    pub const WWW: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_WWW };
+    pub const MAIL: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_MAIL };
+    pub const CALCULATOR: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_CALCULATOR };
+    pub const COMPUTER: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_COMPUTER };
+    pub const AC_SEARCH: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AC_SEARCH };
+    pub const AC_HOME: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AC_HOME };
+    pub const AC_BACK: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AC_BACK };
+    pub const AC_FORWARD: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AC_FORWARD };
+    pub const AC_STOP: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AC_STOP };
+    pub const AC_REFRESH: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AC_REFRESH };
+    pub const AC_BOOKMARKS: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AC_BOOKMARKS };
+    pub const BRIGHTNESSDOWN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_BRIGHTNESSDOWN };
+    pub const BRIGHTNESSUP: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_BRIGHTNESSUP };
+    pub const DISPLAYSWITCH: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_DISPLAYSWITCH };
+    pub const KBDILLUMTOGGLE: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KBDILLUMTOGGLE };
+    pub const KBDILLUMDOWN: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KBDILLUMDOWN };
+    pub const KBDILLUMUP: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_KBDILLUMUP };
+    pub const EJECT: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_EJECT };
+    pub const SLEEP: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_SLEEP };
+    pub const APP1: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_APP1 };
+    pub const APP2: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_APP2 };
+    pub const AUDIOREWIND: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AUDIOREWIND };
+    pub const AUDIOFASTFORWARD: Scancode = Scancode { raw: sdl::SDL_Scancode::SDL_SCANCODE_AUDIOFASTFORWARD };
+}
+
+impl Keycode {
+    pub const UNKNOWN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_UNKNOWN as i32 };
+    pub const RETURN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_RETURN as i32 };
+    pub const ESCAPE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_ESCAPE as i32 };
+    pub const BACKSPACE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_BACKSPACE as i32 };
+    pub const TAB: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_TAB as i32 };
+    pub const SPACE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_SPACE as i32 };
+    pub const EXCLAIM: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_EXCLAIM as i32 };
+    pub const QUOTEDBL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_QUOTEDBL as i32 };
+    pub const HASH: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_HASH as i32 };
+    pub const PERCENT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_PERCENT as i32 };
+    pub const DOLLAR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_DOLLAR as i32 };
+    pub const AMPERSAND: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AMPERSAND as i32 };
+    pub const QUOTE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_QUOTE as i32 };
+    pub const LEFTPAREN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_LEFTPAREN as i32 };
+    pub const RIGHTPAREN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_RIGHTPAREN as i32 };
+    pub const ASTERISK: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_ASTERISK as i32 };
+    pub const PLUS: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_PLUS as i32 };
+    pub const COMMA: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_COMMA as i32 };
+    pub const MINUS: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_MINUS as i32 };
+    pub const PERIOD: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_PERIOD as i32 };
+    pub const SLASH: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_SLASH as i32 };
+    pub const NUM_0: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_0 as i32 };
+    pub const NUM_1: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_1 as i32 };
+    pub const NUM_2: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_2 as i32 };
+    pub const NUM_3: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_3 as i32 };
+    pub const NUM_4: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_4 as i32 };
+    pub const NUM_5: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_5 as i32 };
+    pub const NUM_6: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_6 as i32 };
+    pub const NUM_7: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_7 as i32 };
+    pub const NUM_8: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_8 as i32 };
+    pub const NUM_9: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_9 as i32 };
+    pub const COLON: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_COLON as i32 };
+    pub const SEMICOLON: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_SEMICOLON as i32 };
+    pub const LESS: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_LESS as i32 };
+    pub const EQUALS: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_EQUALS as i32 };
+    pub const GREATER: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_GREATER as i32 };
+    pub const QUESTION: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_QUESTION as i32 };
+    pub const AT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AT as i32 };
+    pub const LEFTBRACKET: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_LEFTBRACKET as i32 };
+    pub const BACKSLASH: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_BACKSLASH as i32 };
+    pub const RIGHTBRACKET: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_RIGHTBRACKET as i32 };
+    pub const CARET: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_CARET as i32 };
+    pub const UNDERSCORE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_UNDERSCORE as i32 };
+    pub const BACKQUOTE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_BACKQUOTE as i32 };
+    pub const A: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_a as i32 };
+    pub const B: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_b as i32 };
+    pub const C: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_c as i32 };
+    pub const D: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_d as i32 };
+    pub const E: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_e as i32 };
+    pub const F: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_f as i32 };
+    pub const G: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_g as i32 };
+    pub const H: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_h as i32 };
+    pub const I: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_i as i32 };
+    pub const J: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_j as i32 };
+    pub const K: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_k as i32 };
+    pub const L: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_l as i32 };
+    pub const M: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_m as i32 };
+    pub const N: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_n as i32 };
+    pub const O: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_o as i32 };
+    pub const P: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_p as i32 };
+    pub const Q: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_q as i32 };
+    pub const R: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_r as i32 };
+    pub const S: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_s as i32 };
+    pub const T: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_t as i32 };
+    pub const U: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_u as i32 };
+    pub const V: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_v as i32 };
+    pub const W: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_w as i32 };
+    pub const X: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_x as i32 };
+    pub const Y: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_y as i32 };
+    pub const Z: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_z as i32 };
+    pub const CAPSLOCK: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_CAPSLOCK as i32 };
+    pub const F1: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F1 as i32 };
+    pub const F2: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F2 as i32 };
+    pub const F3: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F3 as i32 };
+    pub const F4: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F4 as i32 };
+    pub const F5: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F5 as i32 };
+    pub const F6: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F6 as i32 };
+    pub const F7: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F7 as i32 };
+    pub const F8: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F8 as i32 };
+    pub const F9: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F9 as i32 };
+    pub const F10: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F10 as i32 };
+    pub const F11: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F11 as i32 };
+    pub const F12: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F12 as i32 };
+    pub const PRINTSCREEN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_PRINTSCREEN as i32 };
+    pub const SCROLLLOCK: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_SCROLLLOCK as i32 };
+    pub const PAUSE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_PAUSE as i32 };
+    pub const INSERT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_INSERT as i32 };
+    pub const HOME: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_HOME as i32 };
+    pub const PAGEUP: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_PAGEUP as i32 };
+    pub const DELETE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_DELETE as i32 };
+    pub const END: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_END as i32 };
+    pub const PAGEDOWN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_PAGEDOWN as i32 };
+    pub const RIGHT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_RIGHT as i32 };
+    pub const LEFT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_LEFT as i32 };
+    pub const DOWN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_DOWN as i32 };
+    pub const UP: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_UP as i32 };
+    pub const NUMLOCKCLEAR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_NUMLOCKCLEAR as i32 };
+    pub const KP_DIVIDE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_DIVIDE as i32 };
+    pub const KP_MULTIPLY: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_MULTIPLY as i32 };
+    pub const KP_MINUS: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_MINUS as i32 };
+    pub const KP_PLUS: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_PLUS as i32 };
+    pub const KP_ENTER: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_ENTER as i32 };
+    pub const KP_1: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_1 as i32 };
+    pub const KP_2: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_2 as i32 };
+    pub const KP_3: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_3 as i32 };
+    pub const KP_4: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_4 as i32 };
+    pub const KP_5: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_5 as i32 };
+    pub const KP_6: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_6 as i32 };
+    pub const KP_7: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_7 as i32 };
+    pub const KP_8: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_8 as i32 };
+    pub const KP_9: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_9 as i32 };
+    pub const KP_0: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_0 as i32 };
+    pub const KP_PERIOD: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_PERIOD as i32 };
+    pub const APPLICATION: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_APPLICATION as i32 };
+    pub const POWER: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_POWER as i32 };
+    pub const KP_EQUALS: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_EQUALS as i32 };
+    pub const F13: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F13 as i32 };
+    pub const F14: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F14 as i32 };
+    pub const F15: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F15 as i32 };
+    pub const F16: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F16 as i32 };
+    pub const F17: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F17 as i32 };
+    pub const F18: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F18 as i32 };
+    pub const F19: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F19 as i32 };
+    pub const F20: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F20 as i32 };
+    pub const F21: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F21 as i32 };
+    pub const F22: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F22 as i32 };
+    pub const F23: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F23 as i32 };
+    pub const F24: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_F24 as i32 };
+    pub const EXECUTE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_EXECUTE as i32 };
+    pub const HELP: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_HELP as i32 };
+    pub const MENU: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_MENU as i32 };
+    pub const SELECT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_SELECT as i32 };
+    pub const STOP: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_STOP as i32 };
+    pub const AGAIN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AGAIN as i32 };
+    pub const UNDO: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_UNDO as i32 };
+    pub const CUT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_CUT as i32 };
+    pub const COPY: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_COPY as i32 };
+    pub const PASTE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_PASTE as i32 };
+    pub const FIND: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_FIND as i32 };
+    pub const MUTE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_MUTE as i32 };
+    pub const VOLUMEUP: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_VOLUMEUP as i32 };
+    pub const VOLUMEDOWN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_VOLUMEDOWN as i32 };
+    pub const KP_COMMA: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_COMMA as i32 };
+    pub const KP_EQUALSAS400: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_EQUALSAS400 as i32 };
+    pub const ALTERASE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_ALTERASE as i32 };
+    pub const SYSREQ: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_SYSREQ as i32 };
+    pub const CANCEL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_CANCEL as i32 };
+    pub const CLEAR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_CLEAR as i32 };
+    pub const PRIOR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_PRIOR as i32 };
+    pub const RETURN2: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_RETURN2 as i32 };
+    pub const SEPARATOR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_SEPARATOR as i32 };
+    pub const OUT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_OUT as i32 };
+    pub const OPER: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_OPER as i32 };
+    pub const CLEARAGAIN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_CLEARAGAIN as i32 };
+    pub const CRSEL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_CRSEL as i32 };
+    pub const EXSEL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_EXSEL as i32 };
+    pub const KP_00: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_00 as i32 };
+    pub const KP_000: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_000 as i32 };
+    pub const THOUSANDSSEPARATOR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_THOUSANDSSEPARATOR as i32 };
+    pub const DECIMALSEPARATOR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_DECIMALSEPARATOR as i32 };
+    pub const CURRENCYUNIT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_CURRENCYUNIT as i32 };
+    pub const CURRENCYSUBUNIT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_CURRENCYSUBUNIT as i32 };
+    pub const KP_LEFTPAREN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_LEFTPAREN as i32 };
+    pub const KP_RIGHTPAREN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_RIGHTPAREN as i32 };
+    pub const KP_LEFTBRACE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_LEFTBRACE as i32 };
+    pub const KP_RIGHTBRACE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_RIGHTBRACE as i32 };
+    pub const KP_TAB: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_TAB as i32 };
+    pub const KP_BACKSPACE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_BACKSPACE as i32 };
+    pub const KP_A: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_A as i32 };
+    pub const KP_B: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_B as i32 };
+    pub const KP_C: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_C as i32 };
+    pub const KP_D: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_D as i32 };
+    pub const KP_E: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_E as i32 };
+    pub const KP_F: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_F as i32 };
+    pub const KP_XOR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_XOR as i32 };
+    pub const KP_POWER: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_POWER as i32 };
+    pub const KP_PERCENT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_PERCENT as i32 };
+    pub const KP_LESS: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_LESS as i32 };
+    pub const KP_GREATER: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_GREATER as i32 };
+    pub const KP_AMPERSAND: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_AMPERSAND as i32 };
+    pub const KP_DBLAMPERSAND: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_DBLAMPERSAND as i32 };
+    pub const KP_VERTICALBAR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_VERTICALBAR as i32 };
+    pub const KP_DBLVERTICALBAR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_DBLVERTICALBAR as i32 };
+    pub const KP_COLON: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_COLON as i32 };
+    pub const KP_HASH: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_HASH as i32 };
+    pub const KP_SPACE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_SPACE as i32 };
+    pub const KP_AT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_AT as i32 };
+    pub const KP_EXCLAM: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_EXCLAM as i32 };
+    pub const KP_MEMSTORE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_MEMSTORE as i32 };
+    pub const KP_MEMRECALL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_MEMRECALL as i32 };
+    pub const KP_MEMCLEAR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_MEMCLEAR as i32 };
+    pub const KP_MEMADD: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_MEMADD as i32 };
+    pub const KP_MEMSUBTRACT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_MEMSUBTRACT as i32 };
+    pub const KP_MEMMULTIPLY: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_MEMMULTIPLY as i32 };
+    pub const KP_MEMDIVIDE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_MEMDIVIDE as i32 };
+    pub const KP_PLUSMINUS: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_PLUSMINUS as i32 };
+    pub const KP_CLEAR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_CLEAR as i32 };
+    pub const KP_CLEARENTRY: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_CLEARENTRY as i32 };
+    pub const KP_BINARY: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_BINARY as i32 };
+    pub const KP_OCTAL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_OCTAL as i32 };
+    pub const KP_DECIMAL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_DECIMAL as i32 };
+    pub const KP_HEXADECIMAL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KP_HEXADECIMAL as i32 };
+    pub const LCTRL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_LCTRL as i32 };
+    pub const LSHIFT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_LSHIFT as i32 };
+    pub const LALT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_LALT as i32 };
+    pub const LGUI: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_LGUI as i32 };
+    pub const RCTRL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_RCTRL as i32 };
+    pub const RSHIFT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_RSHIFT as i32 };
+    pub const RALT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_RALT as i32 };
+    pub const RGUI: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_RGUI as i32 };
+    pub const MODE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_MODE as i32 };
+    pub const AUDIONEXT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AUDIONEXT as i32 };
+    pub const AUDIOPREV: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AUDIOPREV as i32 };
+    pub const AUDIOSTOP: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AUDIOSTOP as i32 };
+    pub const AUDIOPLAY: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AUDIOPLAY as i32 };
+    pub const AUDIOMUTE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AUDIOMUTE as i32 };
+    pub const MEDIASELECT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_MEDIASELECT as i32 };
+    pub const WWW: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_WWW as i32 };
+    pub const MAIL: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_MAIL as i32 };
+    pub const CALCULATOR: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_CALCULATOR as i32 };
+    pub const COMPUTER: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_COMPUTER as i32 };
+    pub const AC_SEARCH: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AC_SEARCH as i32 };
+    pub const AC_HOME: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AC_HOME as i32 };
+    pub const AC_BACK: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AC_BACK as i32 };
+    pub const AC_FORWARD: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AC_FORWARD as i32 };
+    pub const AC_STOP: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AC_STOP as i32 };
+    pub const AC_REFRESH: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AC_REFRESH as i32 };
+    pub const AC_BOOKMARKS: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AC_BOOKMARKS as i32 };
+    pub const BRIGHTNESSDOWN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_BRIGHTNESSDOWN as i32 };
+    pub const BRIGHTNESSUP: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_BRIGHTNESSUP as i32 };
+    pub const DISPLAYSWITCH: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_DISPLAYSWITCH as i32 };
+    pub const KBDILLUMTOGGLE: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KBDILLUMTOGGLE as i32 };
+    pub const KBDILLUMDOWN: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KBDILLUMDOWN as i32 };
+    pub const KBDILLUMUP: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_KBDILLUMUP as i32 };
+    pub const EJECT: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_EJECT as i32 };
+    pub const SLEEP: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_SLEEP as i32 };
+    pub const APP1: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_APP1 as i32 };
+    pub const APP2: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_APP2 as i32 };
+    pub const AUDIOREWIND: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AUDIOREWIND as i32 };
+    pub const AUDIOFASTFORWARD: Keycode = Keycode { raw: sdl::SDL_KeyCode::SDLK_AUDIOFASTFORWARD as i32 };
+}
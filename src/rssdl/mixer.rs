@@ -0,0 +1,515 @@
+use std::sync::Arc;
+
+use anyhow;
+
+use crate::{
+    audio::{AudioBuffer, AudioCallback, AudioFormat, AudioSpec},
+    events,
+};
+
+/// Pushed as a custom event by `Mixer::poll_finished` every time a channel stopped playing, because
+/// the sound ended, was stopped, faded out or replaced. Read it with `Event::as_custom::<ChannelFinished>()`.
+///
+/// `group` is the group of the channel when it last stopped.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ChannelFinished {
+    pub channel: usize,
+    pub group: Option<GroupId>,
+}
+
+/// A set of channels sharing a volume, e.g. music, sfx and ui.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GroupId(usize);
+
+/// Interleaved stereo samples at the mixer rate, cheap to clone.
+#[derive(Clone)]
+pub struct Sound {
+    samples: Arc<[f32]>,
+}
+
+impl Sound {
+    /// Converts `buffer` to the format of a mixer opened with `mixer_spec`.
+    pub fn from_buffer(buffer: &AudioBuffer, mixer_spec: &AudioSpec) -> anyhow::Result<Sound> {
+        let spec = AudioSpec { format: AudioFormat::f32_sys(), channels: 2, ..*mixer_spec };
+        let converted = buffer.convert(&spec)?;
+        let samples: Arc<[f32]> = converted.bytes().chunks_exact(4).map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]])).collect();
+
+        return Ok(Sound { samples });
+    }
+
+    /// Number of stereo frames.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn frames(&self) -> usize {
+        self.samples.len() / 2
+    }
+}
+
+struct Fade {
+    from: f32,
+    to: f32,
+    elapsed: u32,
+    length: u32,
+    stop: bool,
+}
+
+impl Fade {
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    fn gain(&self) -> f32 {
+        self.from + (self.to - self.from) * (self.elapsed as f32 / self.length as f32)
+    }
+}
+
+struct Channel {
+    sound: Option<Sound>,
+    /// Next frame to play.
+    pos: usize,
+    /// Remaining repeats, -1 forever.
+    loops: i32,
+    volume: f32,
+    pan: f32,
+    group: Option<GroupId>,
+    fade: Option<Fade>,
+    paused: bool,
+    /// Times the channel stopped since the last `poll_finished`, and its group the last time.
+    stops: u32,
+    stop_group: Option<GroupId>,
+    /// The sound that ended on the audio thread, kept so its samples are freed on the main thread.
+    ended: Option<Sound>,
+}
+
+impl Channel {
+    /// Adds the channel to `out`, returns true if the sound ended.
+    fn mix(&mut self, out: &mut [f32], gain: f32) -> bool {
+        let sound = match &self.sound {
+            Some(sound) if !self.paused => sound.clone(),
+            _ => return false,
+        };
+        let left = (1.0 - self.pan).min(1.0) * gain;
+        let right = (1.0 + self.pan).min(1.0) * gain;
+
+        for frame in out.chunks_exact_mut(2) {
+            if self.pos >= sound.frames() {
+                if self.loops == 0 || sound.frames() == 0 {
+                    return true;
+                }
+                if self.loops > 0 {
+                    self.loops -= 1;
+                }
+                self.pos = 0;
+            }
+
+            let mut fade_gain = 1.0;
+            let mut faded_out = false;
+            if let Some(fade) = &mut self.fade {
+                fade_gain = fade.gain();
+                fade.elapsed += 1;
+                if fade.elapsed >= fade.length {
+                    faded_out = fade.stop;
+                    self.fade = None;
+                }
+            }
+
+            frame[0] += sound.samples[self.pos * 2] * left * fade_gain;
+            frame[1] += sound.samples[self.pos * 2 + 1] * right * fade_gain;
+            self.pos += 1;
+            if faded_out {
+                return true;
+            }
+        }
+
+        return false;
+    }
+}
+
+/// Plays any number of `Sound`s at once on a fixed number of channels.
+///
+/// The mixer is the callback of a stereo f32 device, use `AudioDevice::lock` to control it:
+///
+/// ```ignore
+/// let spec = Mixer::spec(48000, 1024);
+/// let mut device = audio.open_playback(None, &spec, Mixer::new(&spec, 16)?)?;
+/// let sine = Sound::from_buffer(&AudioBuffer::load_wav("assets/sine.wav\0")?, &spec)?;
+/// device.resume();
+/// device.lock().play(None, &sine, 0)?;
+///
+/// // once per frame, before polling events
+/// device.lock().poll_finished()?;
+/// ```
+///
+/// Nothing is pushed from the audio thread: the `ChannelFinished` events only arrive through
+/// `poll_finished`, which also frees the sounds that ended on their own.
+pub struct Mixer {
+    freq: i32,
+    channels: Vec<Channel>,
+    group_volumes: Vec<f32>,
+    volume: f32,
+}
+
+impl AudioCallback for Mixer {
+    type Sample = f32;
+
+    fn callback(&mut self, out: &mut [f32]) {
+        out.fill(0.0);
+
+        for index in 0..self.channels.len() {
+            let channel = &self.channels[index];
+            let group_volume = channel.group.map_or(1.0, |g| self.group_volumes[g.0]);
+            let gain = self.volume * channel.volume * group_volume;
+
+            if self.channels[index].mix(out, gain) {
+                self.finish(index);
+            }
+        }
+
+        for sample in out.iter_mut() {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+    }
+}
+
+impl Mixer {
+    /// The device spec a mixer needs, stereo f32 at `freq`.
+    pub fn spec(freq: i32, samples: u16) -> AudioSpec {
+        AudioSpec::new(freq, AudioFormat::f32_sys(), 2, samples)
+    }
+
+    /// Creates a mixer for a device opened with `spec` and registers the `ChannelFinished` event.
+    pub fn new(spec: &AudioSpec, channels: usize) -> anyhow::Result<Mixer> {
+        if spec.format != AudioFormat::f32_sys() || spec.channels != 2 {
+            return Err(anyhow::anyhow!("The mixer needs stereo f32 audio, got {:?} {}({},{})", spec, file!(), line!(), column!()));
+        }
+        events::register_custom_event::<ChannelFinished>()?;
+
+        let channels = (0..channels)
+            .map(|_| Channel {
+                sound: None,
+                pos: 0,
+                loops: 0,
+                volume: 1.0,
+                pan: 0.0,
+                group: None,
+                fade: None,
+                paused: false,
+                stops: 0,
+                stop_group: None,
+                ended: None,
+            })
+            .collect();
+
+        return Ok(Mixer { freq: spec.freq, channels, group_volumes: Vec::new(), volume: 1.0 });
+    }
+
+    fn ms_to_frames(&self, ms: u32) -> u32 {
+        ((self.freq as u64 * ms as u64) / 1000).max(1) as u32
+    }
+
+    /// Stops `channel` and notes it for `poll_finished`. On the audio thread this neither allocates
+    /// nor frees: `ended` is always empty there, `play` empties it before the channel gets a sound.
+    fn finish(&mut self, channel: usize) {
+        let channel_ref = &mut self.channels[channel];
+        let sound = match channel_ref.sound.take() {
+            Some(sound) => sound,
+            None => return,
+        };
+        channel_ref.ended = Some(sound);
+        channel_ref.fade = None;
+        channel_ref.paused = false;
+        channel_ref.stops += 1;
+        channel_ref.stop_group = channel_ref.group;
+    }
+
+    /// Pushes a `ChannelFinished` event for every time a channel stopped since the last call, frees
+    /// the sounds that ended and returns how many events were pushed.
+    ///
+    /// Call it from the main thread through `AudioDevice::lock`.
+    pub fn poll_finished(&mut self) -> anyhow::Result<usize> {
+        let mut pushed = 0;
+        for (index, channel) in self.channels.iter_mut().enumerate() {
+            channel.ended = None;
+            while channel.stops > 0 {
+                events::push_custom_event(ChannelFinished { channel: index, group: channel.stop_group })?;
+                channel.stops -= 1;
+                pushed += 1;
+            }
+        }
+
+        return Ok(pushed);
+    }
+
+    fn check_channel(&self, channel: usize) -> anyhow::Result<()> {
+        if channel >= self.channels.len() {
+            return Err(anyhow::anyhow!("No mixer channel {}, there are {} {}({},{})", channel, self.channels.len(), file!(), line!(), column!()));
+        }
+
+        return Ok(());
+    }
+
+    fn check_group(&self, group: GroupId) -> anyhow::Result<()> {
+        if group.0 >= self.group_volumes.len() {
+            return Err(anyhow::anyhow!("No mixer group {}, there are {} {}({},{})", group.0, self.group_volumes.len(), file!(), line!(), column!()));
+        }
+
+        return Ok(());
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn num_channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Plays `sound` on `channel`, or on the first free channel if it is `None`, and returns the channel.
+    ///
+    /// The sound repeats `loops` more times, -1 loops forever. A sound already playing on the channel is stopped.
+    pub fn play(&mut self, channel: Option<usize>, sound: &Sound, loops: i32) -> anyhow::Result<usize> {
+        let channel = match channel {
+            Some(channel) => channel,
+            None => match self.channels.iter().position(|c| c.sound.is_none()) {
+                Some(channel) => channel,
+                None => return Err(anyhow::anyhow!("No free mixer channel {}({},{})", file!(), line!(), column!())),
+            },
+        };
+        self.check_channel(channel)?;
+        self.finish(channel);
+
+        let channel_ref = &mut self.channels[channel];
+        channel_ref.ended = None;
+        channel_ref.sound = Some(sound.clone());
+        channel_ref.pos = 0;
+        channel_ref.loops = loops.max(-1);
+
+        return Ok(channel);
+    }
+
+    /// Like `play`, but the volume rises from silence over `ms` milliseconds.
+    pub fn fade_in(&mut self, channel: Option<usize>, sound: &Sound, loops: i32, ms: u32) -> anyhow::Result<usize> {
+        let channel = self.play(channel, sound, loops)?;
+        let length = self.ms_to_frames(ms);
+        self.channels[channel].fade = Some(Fade { from: 0.0, to: 1.0, elapsed: 0, length, stop: false });
+
+        return Ok(channel);
+    }
+
+    /// Lowers the volume to silence over `ms` milliseconds, then stops the channel.
+    pub fn fade_out(&mut self, channel: usize, ms: u32) -> anyhow::Result<()> {
+        self.check_channel(channel)?;
+        let length = self.ms_to_frames(ms);
+
+        let channel_ref = &mut self.channels[channel];
+        if channel_ref.sound.is_some() {
+            let from = channel_ref.fade.as_ref().map_or(1.0, |f| f.gain());
+            channel_ref.fade = Some(Fade { from, to: 0.0, elapsed: 0, length, stop: true });
+        }
+
+        return Ok(());
+    }
+
+    pub fn stop(&mut self, channel: usize) -> anyhow::Result<()> {
+        self.check_channel(channel)?;
+        self.finish(channel);
+
+        return Ok(());
+    }
+
+    pub fn stop_all(&mut self) {
+        for channel in 0..self.channels.len() {
+            self.finish(channel);
+        }
+    }
+
+    pub fn pause(&mut self, channel: usize) -> anyhow::Result<()> {
+        self.check_channel(channel)?;
+        self.channels[channel].paused = true;
+
+        return Ok(());
+    }
+
+    pub fn resume(&mut self, channel: usize) -> anyhow::Result<()> {
+        self.check_channel(channel)?;
+        self.channels[channel].paused = false;
+
+        return Ok(());
+    }
+
+    /// True while a sound is on the channel, paused or not.
+    pub fn is_playing(&self, channel: usize) -> bool {
+        self.channels.get(channel).is_some_and(|c| c.sound.is_some())
+    }
+
+    /// Channel volume from 0.0 to 1.0.
+    pub fn set_volume(&mut self, channel: usize, volume: f32) -> anyhow::Result<()> {
+        self.check_channel(channel)?;
+        self.channels[channel].volume = volume.clamp(0.0, 1.0);
+
+        return Ok(());
+    }
+
+    /// Stereo position from -1.0 (left) through 0.0 (center) to 1.0 (right).
+    pub fn set_panning(&mut self, channel: usize, pan: f32) -> anyhow::Result<()> {
+        self.check_channel(channel)?;
+        self.channels[channel].pan = pan.clamp(-1.0, 1.0);
+
+        return Ok(());
+    }
+
+    /// Volume of the whole output from 0.0 to 1.0.
+    pub fn set_master_volume(&mut self, volume: f32) {
+        self.volume = volume.clamp(0.0, 1.0);
+    }
+
+    pub fn create_group(&mut self) -> GroupId {
+        self.group_volumes.push(1.0);
+        GroupId(self.group_volumes.len() - 1)
+    }
+
+    /// Moves `channel` into `group`, or out of any group if it is `None`.
+    pub fn set_channel_group(&mut self, channel: usize, group: Option<GroupId>) -> anyhow::Result<()> {
+        self.check_channel(channel)?;
+        if let Some(group) = group {
+            self.check_group(group)?;
+        }
+        self.channels[channel].group = group;
+
+        return Ok(());
+    }
+
+    /// Group volume from 0.0 to 1.0, multiplied with the volume of each channel in it.
+    pub fn set_group_volume(&mut self, group: GroupId, volume: f32) -> anyhow::Result<()> {
+        self.check_group(group)?;
+        self.group_volumes[group.0] = volume.clamp(0.0, 1.0);
+
+        return Ok(());
+    }
+
+    /// First free channel of `group`, to pass to `play`.
+    pub fn group_available(&self, group: GroupId) -> Option<usize> {
+        self.channels.iter().position(|c| c.group == Some(group) && c.sound.is_none())
+    }
+
+    pub fn stop_group(&mut self, group: GroupId) {
+        for channel in 0..self.channels.len() {
+            if self.channels[channel].group == Some(group) {
+                self.finish(channel);
+            }
+        }
+    }
+
+    pub fn fade_out_group(&mut self, group: GroupId, ms: u32) {
+        for channel in 0..self.channels.len() {
+            if self.channels[channel].group == Some(group) {
+                // the index is in range
+                let _ = self.fade_out(channel, ms);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sdl2_sys as sdl;
+
+    /// At 1000 Hz a millisecond is one frame.
+    fn mixer(channels: usize) -> Mixer {
+        Mixer::new(&Mixer::spec(1000, 64), channels).unwrap()
+    }
+
+    fn sound(frames: &[(f32, f32)]) -> Sound {
+        Sound { samples: frames.iter().flat_map(|&(l, r)| [l, r]).collect() }
+    }
+
+    fn render(mixer: &mut Mixer, frames: usize) -> Vec<(f32, f32)> {
+        let mut out = vec![0.5; frames * 2];
+        mixer.callback(&mut out);
+        return out.chunks_exact(2).map(|f| (f[0], f[1])).collect();
+    }
+
+    #[test]
+    fn volume_panning_and_master_gains() {
+        let mut mixer = mixer(1);
+        mixer.play(Some(0), &sound(&[(1.0, 1.0); 4]), 0).unwrap();
+        mixer.set_volume(0, 0.5).unwrap();
+        mixer.set_panning(0, 0.5).unwrap();
+        mixer.set_master_volume(0.5);
+        assert_eq!(render(&mut mixer, 2), [(0.125, 0.25); 2]);
+
+        mixer.set_panning(0, -1.0).unwrap();
+        assert_eq!(render(&mut mixer, 1), [(0.25, 0.0)]);
+    }
+
+    #[test]
+    fn loops_repeat_the_sound_then_stop() {
+        let mut mixer = mixer(1);
+        mixer.play(Some(0), &sound(&[(0.25, -0.25), (0.5, -0.5)]), 1).unwrap();
+        assert_eq!(render(&mut mixer, 6), [(0.25, -0.25), (0.5, -0.5), (0.25, -0.25), (0.5, -0.5), (0.0, 0.0), (0.0, 0.0)]);
+        assert!(!mixer.is_playing(0));
+
+        mixer.play(Some(0), &sound(&[(0.25, 0.25)]), -1).unwrap();
+        assert_eq!(render(&mut mixer, 3), [(0.25, 0.25); 3]);
+        assert!(mixer.is_playing(0));
+    }
+
+    #[test]
+    fn fades_last_their_length_in_frames() {
+        let mut mixer = mixer(1);
+        let ones = sound(&[(1.0, 1.0); 16]);
+        mixer.fade_in(Some(0), &ones, 0, 4).unwrap();
+        let left: Vec<f32> = render(&mut mixer, 6).iter().map(|f| f.0).collect();
+        assert_eq!(left, [0.0, 0.25, 0.5, 0.75, 1.0, 1.0]);
+
+        mixer.fade_out(0, 4).unwrap();
+        let left: Vec<f32> = render(&mut mixer, 6).iter().map(|f| f.0).collect();
+        assert_eq!(left, [1.0, 0.75, 0.5, 0.25, 0.0, 0.0]);
+        assert!(!mixer.is_playing(0));
+    }
+
+    #[test]
+    fn group_volume_scales_only_its_channels() {
+        let mut mixer = mixer(2);
+        let quiet = sound(&[(0.25, 0.25); 2]);
+        let group = mixer.create_group();
+        mixer.play(Some(0), &quiet, 0).unwrap();
+        mixer.play(Some(1), &quiet, 0).unwrap();
+        mixer.set_channel_group(0, Some(group)).unwrap();
+        mixer.set_group_volume(group, 0.5).unwrap();
+        assert_eq!(render(&mut mixer, 1), [(0.375, 0.375)]);
+
+        assert!(mixer.set_group_volume(GroupId(1), 1.0).is_err());
+        assert!(mixer.set_channel_group(0, Some(GroupId(1))).is_err());
+        assert!(mixer.set_channel_group(2, None).is_err());
+    }
+
+    #[test]
+    fn every_stop_is_reported_and_freed_by_poll_finished() {
+        let _lock = crate::rssdl::sdl_test_lock();
+        unsafe { assert_eq!(sdl::SDL_InitSubSystem(sdl::SDL_INIT_EVENTS), 0) };
+        while events::poll_event().is_some() {}
+
+        let mut mixer = mixer(2);
+        let group = mixer.create_group();
+        mixer.set_channel_group(1, Some(group)).unwrap();
+        let blip = sound(&[(0.25, 0.25)]);
+
+        mixer.play(Some(0), &blip, 0).unwrap();
+        render(&mut mixer, 2);
+        mixer.play(Some(0), &blip, 0).unwrap();
+        render(&mut mixer, 2);
+        mixer.play(Some(1), &blip, -1).unwrap();
+        mixer.stop(1).unwrap();
+        // the ended sound stays alive until the main thread polls
+        assert_eq!(Arc::strong_count(&blip.samples), 3);
+
+        assert_eq!(mixer.poll_finished().unwrap(), 3);
+        assert_eq!(Arc::strong_count(&blip.samples), 1);
+        assert_eq!(mixer.poll_finished().unwrap(), 0);
+
+        let finished: Vec<ChannelFinished> = std::iter::from_fn(events::poll_event).filter_map(|e| e.as_custom::<ChannelFinished>().copied()).collect();
+        let expected = [
+            ChannelFinished { channel: 0, group: None },
+            ChannelFinished { channel: 0, group: None },
+            ChannelFinished { channel: 1, group: Some(group) },
+        ];
+        assert_eq!(finished, expected);
+
+        unsafe { sdl::SDL_QuitSubSystem(sdl::SDL_INIT_EVENTS) };
+    }
+}
@@ -1,8 +1,29 @@
 pub mod core;
 pub mod events;
+pub mod keyboard;
+pub mod clipboard;
+pub mod input;
+pub mod joystick;
+pub mod controller;
+pub mod cursor;
+pub mod touch;
 pub mod renderer;
 pub mod textures;
+pub mod sprite;
+pub mod atlas;
 pub mod surface;
 pub mod timer;
+pub mod audio;
+pub mod mixer;
+pub mod synth;
 pub mod rwops;
 pub mod hint;
+pub(crate) mod json;
+pub mod replay;
+
+/// Serializes the tests that initialize SDL, its init and the event queue are global.
+#[cfg(test)]
+pub(crate) fn sdl_test_lock() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    return LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+}
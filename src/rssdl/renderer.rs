@@ -4,7 +4,7 @@ use sdl2_sys as sdl;
 use crate::{
     core::{Color, IVec2, Rect, Vec2},
     surface::Surface,
-    textures::Texture,
+    textures::{PixelFormat, Texture, TextureAccess},
 };
 
 pub struct SDLContext;
@@ -86,6 +86,15 @@ impl Window {
         }
         return (w, h);
     }
+
+    /// Moves the cursor to window coordinates.
+    #[doc(alias = "SDL_WarpMouseInWindow")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn warp_mouse_in_window(&self, x: i32, y: i32) {
+        unsafe {
+            sdl::SDL_WarpMouseInWindow(self.raw_window, x, y);
+        }
+    }
 }
 
 impl WindowBuilder {
@@ -306,6 +315,18 @@ pub mod WindowFlags {
     pub const KEYBOARD_GRABBED: u32 = sdl::SDL_WindowFlags::SDL_WINDOW_KEYBOARD_GRABBED as u32;
 }
 
+/// Puts the previous render target back when `with_texture_canvas` ends.
+struct RenderTargetRestore {
+    renderer: *mut sdl::SDL_Renderer,
+    previous: *mut sdl::SDL_Texture,
+}
+
+impl Drop for RenderTargetRestore {
+    fn drop(&mut self) {
+        unsafe { sdl::SDL_SetRenderTarget(self.renderer, self.previous) };
+    }
+}
+
 pub struct Renderer {
     raw: *mut sdl::SDL_Renderer,
 }
@@ -339,6 +360,17 @@ impl Renderer {
         }
     }
 
+    /// A texture with undefined pixels, fill it with `Texture::update` or `Texture::with_lock`.
+    #[doc(alias = "SDL_CreateTexture")]
+    pub fn create_texture(&self, format: PixelFormat, access: TextureAccess, width: i32, height: i32) -> anyhow::Result<Texture> {
+        let result = unsafe { sdl::SDL_CreateTexture(self.raw, format.to_ll() as u32, access.to_ll() as i32, width, height) };
+        if result.is_null() {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        } else {
+            return Ok(Texture { raw: result });
+        }
+    }
+
     #[cfg_attr(not(debug_assertions), inline(always))]
     pub fn create_texture_from_surface(&self, surface: &Surface) -> anyhow::Result<Texture> {
         let result = unsafe { sdl::SDL_CreateTextureFromSurface(self.raw, surface.raw) };
@@ -349,9 +381,49 @@ impl Renderer {
         }
     }
 
-    // pub fn warp_mouse_in_window(&self, x: i32, y: i32) {
-    //     unsafe { sdl::SDL_WarpMouseInWindow(self.window, x, y); }
-    // }
+    /// True if the renderer can draw into `TextureAccess::Target` textures. The software renderer
+    /// and most accelerated ones can, `WindowBuilder::target_texture` asks for one that does.
+    #[doc(alias = "SDL_RenderTargetSupported")]
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn render_target_supported(&self) -> bool {
+        unsafe { sdl::SDL_RenderTargetSupported(self.raw) == sdl::SDL_bool::SDL_TRUE }
+    }
+
+    /// Draws into `texture` instead of the window while `f` runs, then switches back to the
+    /// previous target, also when `f` panics. Calls can be nested.
+    ///
+    /// The texture needs `TextureAccess::Target` and the renderer has to support render targets,
+    /// see `render_target_supported`.
+    ///
+    /// ```ignore
+    /// let mut minimap = window.renderer.create_texture(PixelFormat::rgba32(), TextureAccess::Target, 128, 128)?;
+    /// window.renderer.with_texture_canvas(&mut minimap, |r| {
+    ///     r.set_draw_color((0, 0, 0, 255));
+    ///     r.clear();
+    ///     r.fill_rect(&player_dot);
+    /// })?;
+    /// window.renderer.copy(&minimap, Rect::new(0, 0, 128, 128), Rect::new(500, 10, 128, 128));
+    /// ```
+    #[doc(alias = "SDL_SetRenderTarget")]
+    pub fn with_texture_canvas<F, R>(&mut self, texture: &mut Texture, f: F) -> anyhow::Result<R>
+    where
+        F: FnOnce(&mut Renderer) -> R,
+    {
+        if !self.render_target_supported() {
+            return Err(anyhow::anyhow!("The renderer does not support render targets {}({},{})", file!(), line!(), column!()));
+        }
+
+        unsafe {
+            let previous = sdl::SDL_GetRenderTarget(self.raw);
+            if sdl::SDL_SetRenderTarget(self.raw, texture.raw) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+            let _restore = RenderTargetRestore { renderer: self.raw, previous };
+
+            return Ok(f(self));
+        }
+    }
+
     #[cfg_attr(not(debug_assertions), inline(always))]
     pub fn set_draw_color_checked(&self, color: impl Into<Color>) {
         let c = color.into();
@@ -558,3 +630,80 @@ impl Renderer {
         }
     }
 }
+
+/// A software renderer drawing into a surface, so tests need neither a window nor a video driver.
+#[cfg(test)]
+pub(crate) struct SoftwareCanvas {
+    pub renderer: Renderer,
+    _surface: Surface,
+}
+
+#[cfg(test)]
+impl SoftwareCanvas {
+    pub(crate) fn new(width: i32, height: i32) -> SoftwareCanvas {
+        let surface = Surface::new(width, height).unwrap();
+        let raw = unsafe { sdl::SDL_CreateSoftwareRenderer(surface.raw) };
+        assert!(!raw.is_null());
+
+        return SoftwareCanvas { renderer: Renderer { raw }, _surface: surface };
+    }
+}
+
+#[cfg(test)]
+impl Drop for SoftwareCanvas {
+    fn drop(&mut self) {
+        unsafe { sdl::SDL_DestroyRenderer(self.renderer.raw) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(renderer: &Renderer) -> *mut sdl::SDL_Texture {
+        unsafe { sdl::SDL_GetRenderTarget(renderer.raw) }
+    }
+
+    #[test]
+    fn texture_canvas_restores_the_previous_target() {
+        let mut canvas = SoftwareCanvas::new(16, 16);
+        assert!(canvas.renderer.render_target_supported());
+        let mut outer = canvas.renderer.create_texture(PixelFormat::ARGB8888, TextureAccess::Target, 8, 8).unwrap();
+        let mut inner = canvas.renderer.create_texture(PixelFormat::ARGB8888, TextureAccess::Target, 4, 4).unwrap();
+        let (outer_raw, inner_raw) = (outer.raw, inner.raw);
+
+        let value = canvas
+            .renderer
+            .with_texture_canvas(&mut outer, |renderer| {
+                assert_eq!(target(renderer), outer_raw);
+                renderer.with_texture_canvas(&mut inner, |renderer| assert_eq!(target(renderer), inner_raw)).unwrap();
+                assert_eq!(target(renderer), outer_raw);
+                7
+            })
+            .unwrap();
+        assert_eq!(value, 7);
+        assert!(target(&canvas.renderer).is_null());
+
+        let mut streaming = canvas.renderer.create_texture(PixelFormat::ARGB8888, TextureAccess::Streaming, 4, 4).unwrap();
+        assert!(canvas.renderer.with_texture_canvas(&mut streaming, |_| ()).is_err());
+        assert!(target(&canvas.renderer).is_null());
+    }
+
+    #[test]
+    fn texture_canvas_restores_the_target_after_a_panic() {
+        let mut canvas = SoftwareCanvas::new(16, 16);
+        let mut outer = canvas.renderer.create_texture(PixelFormat::ARGB8888, TextureAccess::Target, 8, 8).unwrap();
+        let mut inner = canvas.renderer.create_texture(PixelFormat::ARGB8888, TextureAccess::Target, 4, 4).unwrap();
+        let outer_raw = outer.raw;
+
+        canvas
+            .renderer
+            .with_texture_canvas(&mut outer, |renderer| {
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| renderer.with_texture_canvas(&mut inner, |_| panic!("drawing failed"))));
+                assert!(result.is_err());
+                assert_eq!(target(renderer), outer_raw);
+            })
+            .unwrap();
+        assert!(target(&canvas.renderer).is_null());
+    }
+}
@@ -0,0 +1,752 @@
+use std::{
+    io::{Read, Write},
+    os::raw::c_char,
+};
+
+use anyhow;
+use sdl2_sys as sdl;
+
+use crate::{
+    core::FpsCap,
+    events::{self, Event, RawEvent},
+    keyboard::Scancode,
+    rwops::RWops,
+};
+
+// File layout:
+//   MAGIC, then one record per event:
+//   frame delta (LEB128) | type: u32 LE | timestamp: u32 LE | the fields of that event type, LE
+//   text fields are len: u8 | utf-8 bytes, drop file/text events end with len: u32 LE | utf-8 bytes
+//
+// Only plain data is written field by field, never the raw SDL_Event bytes: pointers and
+// enums (the keysym scancode) can't be trusted when coming back from a file.
+const MAGIC: &[u8; 8] = b"CSDLREC\x02";
+
+trait Field: Copy {
+    const SIZE: usize;
+    fn write(self, buf: &mut Vec<u8>);
+    fn read(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_field {
+    ($($t:ty),*) => {
+        $(
+            impl Field for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn write(self, buf: &mut Vec<u8>) {
+                    buf.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read(bytes: &[u8]) -> $t {
+                    <$t>::from_le_bytes(bytes.try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+impl_field!(u8, i16, u16, u32, i32, i64, u64, f32);
+
+/// Walks the recorded fields of an event, shared by writing and reading so both sides agree on the layout.
+trait Visitor {
+    fn field<T: Field>(&mut self, value: &mut T);
+    fn text(&mut self, text: &mut [c_char; 32]);
+    fn scancode(&mut self, scancode: &mut sdl::SDL_Scancode);
+}
+
+struct Encoder<'a> {
+    buf: &'a mut Vec<u8>,
+}
+
+impl<'a> Visitor for Encoder<'a> {
+    fn field<T: Field>(&mut self, value: &mut T) {
+        value.write(self.buf);
+    }
+
+    fn text(&mut self, text: &mut [c_char; 32]) {
+        let len = text.iter().position(|c| *c == 0).unwrap_or(text.len() - 1);
+        self.buf.push(len as u8);
+        self.buf.extend(text[..len].iter().map(|c| *c as u8));
+    }
+
+    fn scancode(&mut self, scancode: &mut sdl::SDL_Scancode) {
+        (*scancode as i32).write(self.buf);
+    }
+}
+
+struct Decoder<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    ok: bool,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if !self.ok {
+            return None;
+        }
+        let bytes = self.buf.get(self.pos..self.pos + len);
+        match bytes {
+            Some(_) => self.pos += len,
+            None => self.ok = false,
+        }
+
+        return bytes;
+    }
+}
+
+impl<'a> Visitor for Decoder<'a> {
+    fn field<T: Field>(&mut self, value: &mut T) {
+        if let Some(bytes) = self.take(T::SIZE) {
+            *value = T::read(bytes);
+        }
+    }
+
+    fn text(&mut self, text: &mut [c_char; 32]) {
+        let len = match self.take(1) {
+            Some(len) => len[0] as usize,
+            None => return,
+        };
+        // keep room for the terminating zero
+        if len >= text.len() {
+            self.ok = false;
+            return;
+        }
+        if let Some(bytes) = self.take(len) {
+            for (c, b) in text.iter_mut().zip(bytes) {
+                *c = *b as c_char;
+            }
+        }
+    }
+
+    fn scancode(&mut self, scancode: &mut sdl::SDL_Scancode) {
+        let mut code = 0i32;
+        self.field(&mut code);
+        if !self.ok {
+            return;
+        }
+        match Scancode::from_i32(code) {
+            Some(valid) => *scancode = valid.raw,
+            None => self.ok = false,
+        }
+    }
+}
+
+/// Visits the fields of `raw`, `false` if its type is not recorded.
+///
+/// Only event types made of plain data are listed: window manager messages, the
+/// `SDL_TEXTEDITING_EXT` heap string, enum payloads like the battery level and user and
+/// custom events (their pointers mean nothing in another process) are skipped.
+fn visit_fields<V: Visitor>(raw: &mut RawEvent, v: &mut V) -> bool {
+    use sdl::SDL_EventType as T;
+
+    let t = match events::try_type(*raw) {
+        Some(t) => t,
+        None => return false,
+    };
+
+    unsafe {
+        match t {
+            T::SDL_QUIT
+            | T::SDL_APP_TERMINATING
+            | T::SDL_APP_LOWMEMORY
+            | T::SDL_APP_WILLENTERBACKGROUND
+            | T::SDL_APP_DIDENTERBACKGROUND
+            | T::SDL_APP_WILLENTERFOREGROUND
+            | T::SDL_APP_DIDENTERFOREGROUND
+            | T::SDL_LOCALECHANGED
+            | T::SDL_KEYMAPCHANGED
+            | T::SDL_CLIPBOARDUPDATE
+            | T::SDL_RENDER_TARGETS_RESET
+            | T::SDL_RENDER_DEVICE_RESET => {
+                v.field(&mut raw.common.timestamp);
+            }
+            T::SDL_DISPLAYEVENT => {
+                let e = &mut raw.display;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.display);
+                v.field(&mut e.event);
+                v.field(&mut e.data1);
+            }
+            T::SDL_WINDOWEVENT => {
+                let e = &mut raw.window;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.windowID);
+                v.field(&mut e.event);
+                v.field(&mut e.data1);
+                v.field(&mut e.data2);
+            }
+            T::SDL_KEYDOWN | T::SDL_KEYUP => {
+                let e = &mut raw.key;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.windowID);
+                v.field(&mut e.state);
+                v.field(&mut e.repeat);
+                v.scancode(&mut e.keysym.scancode);
+                v.field(&mut e.keysym.sym);
+                v.field(&mut e.keysym.mod_);
+            }
+            T::SDL_TEXTEDITING => {
+                let e = &mut raw.edit;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.windowID);
+                v.text(&mut e.text);
+                v.field(&mut e.start);
+                v.field(&mut e.length);
+            }
+            T::SDL_TEXTINPUT => {
+                let e = &mut raw.text;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.windowID);
+                v.text(&mut e.text);
+            }
+            T::SDL_MOUSEMOTION => {
+                let e = &mut raw.motion;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.windowID);
+                v.field(&mut e.which);
+                v.field(&mut e.state);
+                v.field(&mut e.x);
+                v.field(&mut e.y);
+                v.field(&mut e.xrel);
+                v.field(&mut e.yrel);
+            }
+            T::SDL_MOUSEBUTTONDOWN | T::SDL_MOUSEBUTTONUP => {
+                let e = &mut raw.button;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.windowID);
+                v.field(&mut e.which);
+                v.field(&mut e.button);
+                v.field(&mut e.state);
+                v.field(&mut e.clicks);
+                v.field(&mut e.x);
+                v.field(&mut e.y);
+            }
+            T::SDL_MOUSEWHEEL => {
+                let e = &mut raw.wheel;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.windowID);
+                v.field(&mut e.which);
+                v.field(&mut e.x);
+                v.field(&mut e.y);
+                v.field(&mut e.direction);
+                v.field(&mut e.preciseX);
+                v.field(&mut e.preciseY);
+                v.field(&mut e.mouseX);
+                v.field(&mut e.mouseY);
+            }
+            T::SDL_JOYAXISMOTION => {
+                let e = &mut raw.jaxis;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+                v.field(&mut e.axis);
+                v.field(&mut e.value);
+            }
+            T::SDL_JOYBALLMOTION => {
+                let e = &mut raw.jball;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+                v.field(&mut e.ball);
+                v.field(&mut e.xrel);
+                v.field(&mut e.yrel);
+            }
+            T::SDL_JOYHATMOTION => {
+                let e = &mut raw.jhat;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+                v.field(&mut e.hat);
+                v.field(&mut e.value);
+            }
+            T::SDL_JOYBUTTONDOWN | T::SDL_JOYBUTTONUP => {
+                let e = &mut raw.jbutton;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+                v.field(&mut e.button);
+                v.field(&mut e.state);
+            }
+            T::SDL_JOYDEVICEADDED | T::SDL_JOYDEVICEREMOVED => {
+                let e = &mut raw.jdevice;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+            }
+            T::SDL_CONTROLLERAXISMOTION => {
+                let e = &mut raw.caxis;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+                v.field(&mut e.axis);
+                v.field(&mut e.value);
+            }
+            T::SDL_CONTROLLERBUTTONDOWN | T::SDL_CONTROLLERBUTTONUP => {
+                let e = &mut raw.cbutton;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+                v.field(&mut e.button);
+                v.field(&mut e.state);
+            }
+            T::SDL_CONTROLLERDEVICEADDED | T::SDL_CONTROLLERDEVICEREMOVED | T::SDL_CONTROLLERDEVICEREMAPPED => {
+                let e = &mut raw.cdevice;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+            }
+            T::SDL_CONTROLLERTOUCHPADDOWN | T::SDL_CONTROLLERTOUCHPADMOTION | T::SDL_CONTROLLERTOUCHPADUP => {
+                let e = &mut raw.ctouchpad;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+                v.field(&mut e.touchpad);
+                v.field(&mut e.finger);
+                v.field(&mut e.x);
+                v.field(&mut e.y);
+                v.field(&mut e.pressure);
+            }
+            T::SDL_CONTROLLERSENSORUPDATE => {
+                let e = &mut raw.csensor;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+                v.field(&mut e.sensor);
+                for d in e.data.iter_mut() {
+                    v.field(d);
+                }
+                v.field(&mut e.timestamp_us);
+            }
+            T::SDL_FINGERDOWN | T::SDL_FINGERUP | T::SDL_FINGERMOTION => {
+                let e = &mut raw.tfinger;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.touchId);
+                v.field(&mut e.fingerId);
+                v.field(&mut e.x);
+                v.field(&mut e.y);
+                v.field(&mut e.dx);
+                v.field(&mut e.dy);
+                v.field(&mut e.pressure);
+                v.field(&mut e.windowID);
+            }
+            T::SDL_MULTIGESTURE => {
+                let e = &mut raw.mgesture;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.touchId);
+                v.field(&mut e.dTheta);
+                v.field(&mut e.dDist);
+                v.field(&mut e.x);
+                v.field(&mut e.y);
+                v.field(&mut e.numFingers);
+            }
+            T::SDL_DOLLARGESTURE | T::SDL_DOLLARRECORD => {
+                let e = &mut raw.dgesture;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.touchId);
+                v.field(&mut e.gestureId);
+                v.field(&mut e.numFingers);
+                v.field(&mut e.error);
+                v.field(&mut e.x);
+                v.field(&mut e.y);
+            }
+            // the file/text string is written after the fields, the pointer never is
+            T::SDL_DROPBEGIN | T::SDL_DROPCOMPLETE | T::SDL_DROPFILE | T::SDL_DROPTEXT => {
+                let e = &mut raw.drop;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.windowID);
+            }
+            T::SDL_AUDIODEVICEADDED | T::SDL_AUDIODEVICEREMOVED => {
+                let e = &mut raw.adevice;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+                v.field(&mut e.iscapture);
+            }
+            T::SDL_SENSORUPDATE => {
+                let e = &mut raw.sensor;
+                v.field(&mut e.timestamp);
+                v.field(&mut e.which);
+                for d in e.data.iter_mut() {
+                    v.field(d);
+                }
+                v.field(&mut e.timestamp_us);
+            }
+            _ => return false,
+        }
+    }
+
+    return true;
+}
+
+#[cfg_attr(not(debug_assertions), inline(always))]
+fn is_drop_with_string(raw: &RawEvent) -> bool {
+    let t = unsafe { raw.type_ };
+    t == sdl::SDL_EventType::SDL_DROPFILE as u32 || t == sdl::SDL_EventType::SDL_DROPTEXT as u32
+}
+
+/// Records the event stream together with the `FpsCap` frame each event was polled on.
+///
+/// Use `EventRecorder::poll_event` in place of `events::poll_event`, then `save` the
+/// recording and feed it to `EventReplay` to get the same events at the same frames.
+pub struct EventRecorder {
+    buf: Vec<u8>,
+    last_frame: u64,
+    count: usize,
+}
+
+impl EventRecorder {
+    pub fn new() -> EventRecorder {
+        EventRecorder { buf: MAGIC.to_vec(), last_frame: 0, count: 0 }
+    }
+
+    /// Same as `events::poll_event`, but records the event on `fps.frame()`.
+    pub fn poll_event(&mut self, fps: &FpsCap) -> anyhow::Result<Option<Event>> {
+        let raw = match events::poll_event_raw() {
+            Some(raw) => raw,
+            None => return Ok(None),
+        };
+        let recorded = self.record(fps.frame(), &raw);
+        let event = Event::from_ll(&raw);
+        unsafe { events::free_raw_event(&raw) };
+        recorded?;
+
+        return Ok(Some(event));
+    }
+
+    /// Records `raw` on `frame`, for callers polling with `events::poll_event_raw`.
+    ///
+    /// Event types that aren't plain data are skipped, see `visit_fields`. Errors if
+    /// `frame` is before the frame of the previous event.
+    pub fn record(&mut self, frame: u64, raw: &RawEvent) -> anyhow::Result<()> {
+        if frame < self.last_frame {
+            return Err(anyhow::anyhow!("Frame {} recorded after frame {} {}({},{})", frame, self.last_frame, file!(), line!(), column!()));
+        }
+
+        let start = self.buf.len();
+        write_varint(&mut self.buf, frame - self.last_frame);
+        unsafe { raw.type_ }.write(&mut self.buf);
+        let mut copy = *raw;
+        if !visit_fields(&mut copy, &mut Encoder { buf: &mut self.buf }) {
+            self.buf.truncate(start);
+            return Ok(());
+        }
+
+        if is_drop_with_string(raw) {
+            let text = unsafe {
+                if raw.drop.file.is_null() {
+                    &[][..]
+                } else {
+                    std::ffi::CStr::from_ptr(raw.drop.file).to_bytes()
+                }
+            };
+            (text.len() as u32).write(&mut self.buf);
+            self.buf.extend_from_slice(text);
+        }
+
+        self.last_frame = frame;
+        self.count += 1;
+
+        return Ok(());
+    }
+
+    /// Number of recorded events.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// The recording as `save` writes it, `EventReplay::from_bytes` reads it back.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Writes the recording, e.g. to `RWops::from_file("replay.bin\0", "wb\0")`.
+    pub fn save(&self, rwops: &mut RWops) -> anyhow::Result<()> {
+        if let Err(ioerror) = rwops.write_all(&self.buf) {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", ioerror, file!(), line!(), column!()));
+        }
+
+        return Ok(());
+    }
+}
+
+struct Record {
+    frame: u64,
+    raw: RawEvent,
+    text: Option<Vec<u8>>,
+}
+
+/// Plays back a recording made by `EventRecorder` by pushing its events into the
+/// queue with `SDL_PushEvent` on the frames they were recorded on.
+///
+/// Only the event stream is reproduced: `KeyboardState` and `MouseState` are not
+/// updated by pushed events. Works under `SDL_VIDEODRIVER=dummy`.
+pub struct EventReplay {
+    records: Vec<Record>,
+    next: usize,
+}
+
+impl EventReplay {
+    pub fn load(rwops: &mut RWops) -> anyhow::Result<EventReplay> {
+        let mut buf = Vec::new();
+        if let Err(ioerror) = rwops.read_to_end(&mut buf) {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", ioerror, file!(), line!(), column!()));
+        }
+
+        return EventReplay::from_bytes(&buf);
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> anyhow::Result<EventReplay> {
+        if buf.len() < MAGIC.len() || &buf[..MAGIC.len()] != MAGIC {
+            return Err(anyhow::anyhow!("Not an event recording {}({},{})", file!(), line!(), column!()));
+        }
+
+        let mut records = Vec::new();
+        let mut frame = 0u64;
+        let mut pos = MAGIC.len();
+        while pos < buf.len() {
+            let delta = read_varint(buf, &mut pos).ok_or_else(|| corrupt(pos))?;
+            frame += delta;
+
+            let mut decoder = Decoder { buf, pos, ok: true };
+            let mut type_ = 0u32;
+            decoder.field(&mut type_);
+            let mut raw: RawEvent = unsafe { std::mem::zeroed() };
+            raw.type_ = type_;
+            if !visit_fields(&mut raw, &mut decoder) || !decoder.ok {
+                return Err(corrupt(pos));
+            }
+
+            let text = if is_drop_with_string(&raw) {
+                let mut text_len = 0u32;
+                decoder.field(&mut text_len);
+                match decoder.take(text_len as usize) {
+                    Some(text) => Some(text.to_vec()),
+                    None => return Err(corrupt(pos)),
+                }
+            } else {
+                None
+            };
+            pos = decoder.pos;
+
+            records.push(Record { frame, raw, text });
+        }
+
+        return Ok(EventReplay { records, next: 0 });
+    }
+
+    /// Pushes every event recorded up to and including `fps.frame()` that was not pushed yet.
+    ///
+    /// Call it right after `FpsCap::start`, before polling. Returns the number of pushed events.
+    pub fn push_frame(&mut self, fps: &FpsCap) -> anyhow::Result<usize> {
+        return self.push_until(fps.frame());
+    }
+
+    pub fn push_until(&mut self, frame: u64) -> anyhow::Result<usize> {
+        let mut pushed = 0;
+        while let Some(record) = self.records.get(self.next) {
+            if record.frame > frame {
+                break;
+            }
+
+            let mut raw = record.raw;
+            unsafe {
+                if let Some(text) = &record.text {
+                    // freed by whoever polls the event, like the strings SDL allocates itself
+                    let file = sdl::SDL_malloc((text.len() + 1) as _) as *mut u8;
+                    if file.is_null() {
+                        return Err(anyhow::anyhow!("Out of memory {}({},{})", file!(), line!(), column!()));
+                    }
+                    std::ptr::copy_nonoverlapping(text.as_ptr(), file, text.len());
+                    *file.add(text.len()) = 0;
+                    raw.drop.file = file as *mut _;
+                }
+
+                let ret = sdl::SDL_PushEvent(&mut raw);
+                if ret != 1 {
+                    events::free_raw_event(&raw);
+                }
+                if ret < 0 {
+                    return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+                }
+            }
+
+            self.next += 1;
+            pushed += 1;
+        }
+
+        return Ok(pushed);
+    }
+
+    /// Frame of the last recorded event.
+    pub fn last_frame(&self) -> Option<u64> {
+        self.records.last().map(|r| r.frame)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.records.len()
+    }
+
+    /// Starts the playback over from the first event.
+    pub fn rewind(&mut self) {
+        self.next = 0;
+    }
+}
+
+fn corrupt(pos: usize) -> anyhow::Error {
+    anyhow::anyhow!("Corrupt event recording at byte {} {}({},{})", pos, file!(), line!(), column!())
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn read_varint(buf: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *buf.get(*pos)?;
+        *pos += 1;
+        if shift >= 64 {
+            return None;
+        }
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+        shift += 7;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw_event(type_: sdl::SDL_EventType) -> RawEvent {
+        let mut raw: RawEvent = unsafe { std::mem::zeroed() };
+        raw.type_ = type_ as u32;
+
+        return raw;
+    }
+
+    fn set_text(text: &mut [c_char; 32], s: &str) {
+        for (c, b) in text.iter_mut().zip(s.bytes()) {
+            *c = b as c_char;
+        }
+    }
+
+    /// The event a replayed record turns into once polled, with the drop string handed back like SDL does.
+    fn replayed(record: &Record) -> Event {
+        let text = record.text.as_ref().map(|text| std::ffi::CString::new(text.clone()).unwrap());
+        let mut raw = record.raw;
+        if let Some(text) = &text {
+            raw.drop.file = text.as_ptr() as *mut c_char;
+        }
+
+        return Event::from_ll(&raw);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut key = raw_event(sdl::SDL_EventType::SDL_KEYDOWN);
+        key.key.timestamp = 17;
+        key.key.windowID = 1;
+        key.key.state = sdl::SDL_PRESSED as u8;
+        key.key.repeat = 1;
+        key.key.keysym.scancode = sdl::SDL_Scancode::SDL_SCANCODE_SPACE;
+        key.key.keysym.sym = sdl::SDL_KeyCode::SDLK_SPACE as i32;
+        key.key.keysym.mod_ = sdl::SDL_Keymod::KMOD_LSHIFT as u16;
+
+        let mut motion = raw_event(sdl::SDL_EventType::SDL_MOUSEMOTION);
+        motion.motion.timestamp = 18;
+        motion.motion.windowID = 1;
+        motion.motion.state = 1;
+        motion.motion.x = 320;
+        motion.motion.y = -4;
+        motion.motion.xrel = 7;
+        motion.motion.yrel = -2;
+
+        let mut button = raw_event(sdl::SDL_EventType::SDL_MOUSEBUTTONUP);
+        button.button.timestamp = 19;
+        button.button.windowID = 1;
+        button.button.button = sdl::SDL_BUTTON_RIGHT as u8;
+        button.button.clicks = 2;
+        button.button.x = 10;
+        button.button.y = 20;
+
+        let mut wheel = raw_event(sdl::SDL_EventType::SDL_MOUSEWHEEL);
+        wheel.wheel.timestamp = 20;
+        wheel.wheel.y = -1;
+        wheel.wheel.preciseY = -0.5;
+
+        let mut text = raw_event(sdl::SDL_EventType::SDL_TEXTINPUT);
+        text.text.timestamp = 21;
+        text.text.windowID = 1;
+        set_text(unsafe { &mut text.text.text }, "héllo");
+
+        let mut window = raw_event(sdl::SDL_EventType::SDL_WINDOWEVENT);
+        window.window.timestamp = 22;
+        window.window.windowID = 1;
+        window.window.event = sdl::SDL_WindowEventID::SDL_WINDOWEVENT_RESIZED as u8;
+        window.window.data1 = 800;
+        window.window.data2 = 600;
+
+        let file = std::ffi::CString::new("/tmp/level 1.map").unwrap();
+        let mut drop = raw_event(sdl::SDL_EventType::SDL_DROPFILE);
+        drop.drop.timestamp = 23;
+        drop.drop.windowID = 1;
+        drop.drop.file = file.as_ptr() as *mut c_char;
+
+        let mut quit = raw_event(sdl::SDL_EventType::SDL_QUIT);
+        quit.common.timestamp = 24;
+
+        let recorded = [(0, key), (0, motion), (3, button), (3, wheel), (4, text), (130, window), (130, drop), (200, quit)];
+        let mut recorder = EventRecorder::new();
+        for (frame, raw) in recorded.iter() {
+            recorder.record(*frame, raw).unwrap();
+        }
+        assert_eq!(recorder.len(), recorded.len());
+
+        let replay = EventReplay::from_bytes(recorder.as_bytes()).unwrap();
+        assert_eq!(replay.records.len(), recorded.len());
+        for ((frame, raw), record) in recorded.iter().zip(replay.records.iter()) {
+            assert_eq!(record.frame, *frame);
+            assert_eq!(replayed(record), Event::from_ll(raw));
+        }
+        assert_eq!(replay.last_frame(), Some(200));
+    }
+
+    #[test]
+    fn skips_events_that_are_not_plain_data() {
+        let mut recorder = EventRecorder::new();
+        recorder.record(0, &raw_event(sdl::SDL_EventType::SDL_SYSWMEVENT)).unwrap();
+        recorder.record(0, &raw_event(sdl::SDL_EventType::SDL_TEXTEDITING_EXT)).unwrap();
+        recorder.record(0, &raw_event(sdl::SDL_EventType::SDL_USEREVENT)).unwrap();
+
+        assert!(recorder.is_empty());
+        assert_eq!(recorder.as_bytes(), &MAGIC[..]);
+    }
+
+    #[test]
+    fn rejects_frames_going_backwards() {
+        let mut recorder = EventRecorder::new();
+        recorder.record(5, &raw_event(sdl::SDL_EventType::SDL_QUIT)).unwrap();
+
+        assert!(recorder.record(4, &raw_event(sdl::SDL_EventType::SDL_QUIT)).is_err());
+        assert_eq!(recorder.len(), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_scancodes_and_truncated_records() {
+        let mut recorder = EventRecorder::new();
+        recorder.record(0, &raw_event(sdl::SDL_EventType::SDL_KEYDOWN)).unwrap();
+        let bytes = recorder.as_bytes().to_vec();
+
+        // magic, frame delta, type, timestamp, windowID, state, repeat, then the scancode
+        let scancode = MAGIC.len() + 1 + 4 + 4 + 4 + 1 + 1;
+        let mut invalid = bytes.clone();
+        invalid[scancode..scancode + 4].copy_from_slice(&1000i32.to_le_bytes());
+        assert!(EventReplay::from_bytes(&invalid).is_err());
+
+        assert!(EventReplay::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(EventReplay::from_bytes(&bytes).is_ok());
+    }
+}
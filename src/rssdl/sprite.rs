@@ -0,0 +1,737 @@
+use std::{collections::HashMap, io::Read, path::Path};
+
+use anyhow;
+
+use crate::{
+    core::{IVec2, Rect, Vec2},
+    json,
+    renderer::Renderer,
+    rwops::RWops,
+    textures::Texture,
+};
+
+/// Frame duration in seconds for animations of exports that don't store one.
+pub const DEFAULT_FRAME_DURATION: f32 = 0.1;
+
+/// Where a frame is on the sheet texture and how it sits in the sprite it was cut from.
+///
+/// Trimmed frames only hold the non transparent part of the sprite, `offset` places `rect`
+/// inside the untrimmed `source_size`.
+#[derive(Copy, Clone, Debug)]
+pub struct Frame {
+    pub rect: Rect,
+    pub offset: IVec2,
+    pub source_size: IVec2,
+    /// Point the sprite is anchored at, normalized to the source size, (0.5, 0.5) is the center.
+    pub pivot: Vec2,
+    /// Seconds to show the frame as exported, 0.0 if the export has none.
+    pub duration: f32,
+}
+
+impl Frame {
+    /// An untrimmed frame.
+    pub fn new(rect: Rect) -> Frame {
+        Frame { rect, offset: IVec2::new(0, 0), source_size: IVec2::new(rect.raw.w, rect.raw.h), pivot: Vec2::new(0.5, 0.5), duration: 0.0 }
+    }
+
+    pub fn is_trimmed(&self) -> bool {
+        self.offset.x != 0 || self.offset.y != 0 || self.source_size.x != self.rect.raw.w || self.source_size.y != self.rect.raw.h
+    }
+
+    /// The part of `dst` the trimmed rect covers when the whole source is stretched over `dst`.
+    pub fn trimmed_dst(&self, dst: Rect, flip_horizontal: bool, flip_vertical: bool) -> Rect {
+        if !self.is_trimmed() || self.source_size.x <= 0 || self.source_size.y <= 0 {
+            return dst;
+        }
+        let scale_x = dst.raw.w as f32 / self.source_size.x as f32;
+        let scale_y = dst.raw.h as f32 / self.source_size.y as f32;
+
+        let offset_x = if flip_horizontal { self.source_size.x - self.offset.x - self.rect.raw.w } else { self.offset.x };
+        let offset_y = if flip_vertical { self.source_size.y - self.offset.y - self.rect.raw.h } else { self.offset.y };
+
+        return Rect::new(
+            dst.raw.x + (offset_x as f32 * scale_x).round() as i32,
+            dst.raw.y + (offset_y as f32 * scale_y).round() as i32,
+            (self.rect.raw.w as f32 * scale_x).round() as i32,
+            (self.rect.raw.h as f32 * scale_y).round() as i32,
+        );
+    }
+
+    /// Where the pivot lands when the whole source is stretched over `dst`, flips mirror it.
+    pub fn pivot_point(&self, dst: Rect, flip_horizontal: bool, flip_vertical: bool) -> IVec2 {
+        let x = if flip_horizontal { 1.0 - self.pivot.x } else { self.pivot.x };
+        let y = if flip_vertical { 1.0 - self.pivot.y } else { self.pivot.y };
+
+        return IVec2::new(dst.raw.x + (x * dst.raw.w as f32).round() as i32, dst.raw.y + (y * dst.raw.h as f32).round() as i32);
+    }
+}
+
+/// A sheet and the animations read from an Aseprite or TexturePacker export.
+pub struct ImportedSheet {
+    pub sheet: SpriteSheet,
+    pub animations: HashMap<String, Animation>,
+    /// The image file named in the export, relative to the JSON file.
+    pub image: Option<String>,
+}
+
+/// A texture sliced into frames, addressed by index or by name.
+pub struct SpriteSheet {
+    pub texture: Texture,
+    frames: Vec<Frame>,
+    names: Vec<(String, usize)>,
+}
+
+impl SpriteSheet {
+    /// A sheet without frames, add them with `add_frame` or `add_named_frame`.
+    pub fn new(texture: Texture) -> SpriteSheet {
+        SpriteSheet { texture, frames: Vec::new(), names: Vec::new() }
+    }
+
+    /// Slices the whole texture into `frame_width` x `frame_height` cells, row by row.
+    ///
+    /// The frame at `column`, `row` has the index `row * columns + column`. Cells cut off by
+    /// the texture border are kept, drawing clips them to the texture.
+    pub fn from_grid(texture: Texture, frame_width: i32, frame_height: i32) -> anyhow::Result<SpriteSheet> {
+        if frame_width <= 0 || frame_height <= 0 {
+            return Err(anyhow::anyhow!("Invalid frame size {}x{} {}({},{})", frame_width, frame_height, file!(), line!(), column!()));
+        }
+
+        let query = texture.query()?;
+        let (width, height) = (query.width, query.height);
+
+        let mut sheet = SpriteSheet::new(texture);
+        let columns = (width + frame_width - 1) / frame_width;
+        let rows = (height + frame_height - 1) / frame_height;
+        for row in 0..rows {
+            for column in 0..columns {
+                sheet.add_frame(Rect::new(column * frame_width, row * frame_height, frame_width, frame_height));
+            }
+        }
+
+        return Ok(sheet);
+    }
+
+    /// Adds a frame and returns its index.
+    pub fn add_frame(&mut self, rect: Rect) -> usize {
+        self.add_frame_info(Frame::new(rect))
+    }
+
+    /// Adds a trimmed frame or one with a pivot, returns its index.
+    pub fn add_frame_info(&mut self, frame: Frame) -> usize {
+        self.frames.push(frame);
+        self.frames.len() - 1
+    }
+
+    /// Adds a frame that can be looked up with `frame_index`, returns its index.
+    pub fn add_named_frame(&mut self, name: &str, rect: Rect) -> usize {
+        let index = self.add_frame(rect);
+        self.names.push((name.to_owned(), index));
+        index
+    }
+
+    /// Names an existing frame, e.g. a grid cell.
+    pub fn set_frame_name(&mut self, name: &str, index: usize) {
+        match self.names.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = index,
+            None => self.names.push((name.to_owned(), index)),
+        }
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn frame(&self, index: usize) -> Rect {
+        self.frames[index].rect
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn frame_info(&self, index: usize) -> &Frame {
+        &self.frames[index]
+    }
+
+    pub fn frame_index(&self, name: &str) -> Option<usize> {
+        self.names.iter().find(|(n, _)| n == name).map(|(_, index)| *index)
+    }
+
+    pub fn frame_by_name(&self, name: &str) -> Option<Rect> {
+        self.frame_index(name).map(|index| self.frames[index].rect)
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Draws frame `index` stretched over `dst`, a trimmed frame only covers its part of `dst`.
+    pub fn draw(&self, renderer: &mut Renderer, index: usize, dst: Rect, flip_horizontal: bool, flip_vertical: bool) {
+        self.draw_rotated(renderer, index, dst, 0.0, flip_horizontal, flip_vertical);
+    }
+
+    /// Like `draw`, rotated clockwise by `angle` degrees around the frame pivot.
+    pub fn draw_rotated(&self, renderer: &mut Renderer, index: usize, dst: Rect, angle: f64, flip_horizontal: bool, flip_vertical: bool) {
+        let frame = &self.frames[index];
+        let pivot = frame.pivot_point(dst, flip_horizontal, flip_vertical);
+        let dst = frame.trimmed_dst(dst, flip_horizontal, flip_vertical);
+        let center = IVec2::new(pivot.x - dst.raw.x, pivot.y - dst.raw.y);
+        renderer.copy_ex(&self.texture, frame.rect, dst, angle, center, flip_horizontal, flip_vertical);
+    }
+
+    /// Draws frame `index` at its source size times `scale` with the pivot on `position`, e.g. the
+    /// feet of a character on the ground point whatever the frame is trimmed to.
+    pub fn draw_at(&self, renderer: &mut Renderer, index: usize, position: IVec2, scale: f32, flip_horizontal: bool, flip_vertical: bool) {
+        let frame = &self.frames[index];
+        let size = Rect::new(0, 0, (frame.source_size.x as f32 * scale).round() as i32, (frame.source_size.y as f32 * scale).round() as i32);
+        let pivot = frame.pivot_point(size, flip_horizontal, flip_vertical);
+        let dst = Rect::new(position.x - pivot.x, position.y - pivot.y, size.raw.w, size.raw.h);
+        self.draw(renderer, index, dst, flip_horizontal, flip_vertical);
+    }
+
+    pub fn load_json<P: AsRef<Path>>(texture: Texture, path: P) -> anyhow::Result<ImportedSheet> {
+        let mut file = RWops::from_file(path, "rb\0")?;
+        return SpriteSheet::load_json_rw(texture, &mut file);
+    }
+
+    /// Reads the "JSON (Hash)" or "JSON (Array)" sheet export of Aseprite or TexturePacker, the
+    /// rects are against `texture`, e.g. from `Renderer::load_texture_from_file`.
+    ///
+    /// Frames keep the export order and are named by their key or `filename`. Aseprite
+    /// `frameTags` and TexturePacker/pixi `animations` become `Animation`s, frames without a
+    /// `duration` are shown for `DEFAULT_FRAME_DURATION`. Pivots come from the TexturePacker
+    /// `pivot` of a frame or from the first Aseprite slice that has one. Rotated frames are
+    /// not supported, export without rotation.
+    pub fn load_json_rw(texture: Texture, rwops: &mut RWops) -> anyhow::Result<ImportedSheet> {
+        let mut text = String::new();
+        if let Err(ioerror) = rwops.read_to_string(&mut text) {
+            return Err(anyhow::anyhow!("{:?} {}({},{})", ioerror, file!(), line!(), column!()));
+        }
+        let root = json::parse(&text)?;
+
+        let entries: Vec<(&str, &json::Value)> = match root.get("frames") {
+            Some(json::Value::Object(members)) => members.iter().map(|(name, frame)| (name.as_str(), frame)).collect(),
+            Some(json::Value::Array(items)) => items.iter().map(|frame| (frame.get("filename").and_then(|f| f.as_str()).unwrap_or(""), frame)).collect(),
+            _ => return Err(anyhow::anyhow!("Sheet JSON has no \"frames\" {}({},{})", file!(), line!(), column!())),
+        };
+
+        let mut sheet = SpriteSheet::new(texture);
+        for (name, entry) in entries {
+            if entry.get("rotated").and_then(|r| r.as_bool()) == Some(true) {
+                return Err(anyhow::anyhow!("Sheet frame {:?} is rotated {}({},{})", name, file!(), line!(), column!()));
+            }
+            let rect = match entry.get("frame").and_then(json_rect) {
+                Some(rect) => rect,
+                None => return Err(anyhow::anyhow!("Sheet frame {:?} has no \"frame\" rect {}({},{})", name, file!(), line!(), column!())),
+            };
+
+            let mut frame = Frame::new(rect);
+            if entry.get("trimmed").and_then(|t| t.as_bool()) == Some(true) {
+                if let Some(sprite_source) = entry.get("spriteSourceSize").and_then(json_rect) {
+                    frame.offset = IVec2::new(sprite_source.raw.x, sprite_source.raw.y);
+                }
+                if let Some(size) = entry.get("sourceSize").and_then(json_size) {
+                    frame.source_size = size;
+                }
+            }
+            if let Some(pivot) = entry.get("pivot") {
+                let x = pivot.get("x").and_then(|x| x.as_f64()).unwrap_or(0.5);
+                let y = pivot.get("y").and_then(|y| y.as_f64()).unwrap_or(0.5);
+                frame.pivot = Vec2::new(x as f32, y as f32);
+            }
+            if let Some(ms) = entry.get("duration").and_then(|d| d.as_f64()) {
+                frame.duration = ms as f32 / 1000.0;
+            }
+
+            if name.is_empty() {
+                sheet.add_frame_info(frame);
+            } else {
+                let index = sheet.add_frame_info(frame);
+                sheet.set_frame_name(name, index);
+            }
+        }
+
+        let meta = root.get("meta");
+        if let Some(slice) = meta.and_then(|m| m.get("slices")).and_then(|s| s.as_array()).and_then(|s| s.iter().find(|slice| json_slice_has_pivot(slice))) {
+            sheet.apply_slice_pivots(slice);
+        }
+
+        let mut animations = HashMap::new();
+        for tag in meta.and_then(|m| m.get("frameTags")).and_then(|t| t.as_array()).unwrap_or(&[]) {
+            let name = tag.get("name").and_then(|n| n.as_str()).unwrap_or("");
+            let from = tag.get("from").and_then(|f| f.as_i32()).unwrap_or(0).max(0) as usize;
+            let to = tag.get("to").and_then(|t| t.as_i32()).unwrap_or(0).max(0) as usize;
+            if from > to || to >= sheet.len() {
+                return Err(anyhow::anyhow!("Sheet tag {:?} frames {}..={} out of range {}({},{})", name, from, to, file!(), line!(), column!()));
+            }
+
+            let direction = tag.get("direction").and_then(|d| d.as_str()).unwrap_or("forward");
+            let mode = if direction.starts_with("pingpong") { PlayMode::PingPong } else { PlayMode::Loop };
+            let mut frames: Vec<usize> = (from..=to).collect();
+            if direction == "reverse" || direction == "pingpong_reverse" {
+                frames.reverse();
+            }
+            animations.insert(name.to_owned(), sheet.animation(&frames, mode));
+        }
+        for (name, names) in meta.and_then(|m| m.get("animations")).or(root.get("animations")).and_then(|a| a.as_object()).unwrap_or(&[]) {
+            let mut frames = Vec::new();
+            for frame_name in names.as_array().unwrap_or(&[]) {
+                let frame_name = frame_name.as_str().unwrap_or("");
+                match sheet.frame_index(frame_name) {
+                    Some(index) => frames.push(index),
+                    None => return Err(anyhow::anyhow!("Sheet animation {:?} uses unknown frame {:?} {}({},{})", name, frame_name, file!(), line!(), column!())),
+                }
+            }
+            animations.insert(name.clone(), sheet.animation(&frames, PlayMode::Loop));
+        }
+
+        let image = meta.and_then(|m| m.get("image")).and_then(|i| i.as_str()).map(|i| i.to_owned());
+
+        return Ok(ImportedSheet { sheet, animations, image });
+    }
+
+    /// An animation of `frames` with their exported durations.
+    pub fn animation(&self, frames: &[usize], mode: PlayMode) -> Animation {
+        let mut animation = Animation::new(mode);
+        for &index in frames {
+            let duration = self.frames[index].duration;
+            animation.add_frame(index, if duration > 0.0 { duration } else { DEFAULT_FRAME_DURATION });
+        }
+
+        return animation;
+    }
+
+    /// Aseprite slice keys hold a pivot in pixels that applies from their frame on.
+    fn apply_slice_pivots(&mut self, slice: &json::Value) {
+        let mut keys: Vec<(usize, Vec2)> = Vec::new();
+        for key in slice.get("keys").and_then(|k| k.as_array()).unwrap_or(&[]) {
+            if let (Some(bounds), Some(pivot)) = (key.get("bounds").and_then(json_rect), key.get("pivot")) {
+                let x = bounds.raw.x as f64 + pivot.get("x").and_then(|x| x.as_f64()).unwrap_or(0.0);
+                let y = bounds.raw.y as f64 + pivot.get("y").and_then(|y| y.as_f64()).unwrap_or(0.0);
+                let frame = key.get("frame").and_then(|f| f.as_i32()).unwrap_or(0).max(0) as usize;
+                keys.push((frame, Vec2::new(x as f32, y as f32)));
+            }
+        }
+        keys.sort_by_key(|(frame, _)| *frame);
+
+        for (index, frame) in self.frames.iter_mut().enumerate() {
+            if let Some((_, pivot)) = keys.iter().rev().find(|(key_frame, _)| *key_frame <= index) {
+                if frame.source_size.x > 0 && frame.source_size.y > 0 {
+                    frame.pivot = Vec2::new(pivot.x / frame.source_size.x as f32, pivot.y / frame.source_size.y as f32);
+                }
+            }
+        }
+    }
+}
+
+fn json_rect(value: &json::Value) -> Option<Rect> {
+    let field = |name: &str| value.get(name).and_then(|v| v.as_i32());
+
+    return Some(Rect::new(field("x")?, field("y")?, field("w")?, field("h")?));
+}
+
+fn json_size(value: &json::Value) -> Option<IVec2> {
+    let field = |name: &str| value.get(name).and_then(|v| v.as_i32());
+
+    return Some(IVec2::new(field("w")?, field("h")?));
+}
+
+fn json_slice_has_pivot(slice: &json::Value) -> bool {
+    slice.get("keys").and_then(|k| k.as_array()).is_some_and(|keys| keys.iter().any(|key| key.get("pivot").is_some()))
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PlayMode {
+    /// Starts over after the last frame.
+    Loop,
+    /// Plays forward then backward, forever.
+    PingPong,
+    /// Stops on the last frame.
+    Once,
+}
+
+/// A sequence of sheet frames, each shown for its own duration in seconds.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Animation {
+    frames: Vec<(usize, f32)>,
+    pub mode: PlayMode,
+}
+
+impl Animation {
+    pub fn new(mode: PlayMode) -> Animation {
+        Animation { frames: Vec::new(), mode }
+    }
+
+    /// All `frames` shown for `frame_duration` seconds.
+    pub fn from_frames(frames: &[usize], frame_duration: f32, mode: PlayMode) -> Animation {
+        Animation { frames: frames.iter().map(|f| (*f, frame_duration)).collect(), mode }
+    }
+
+    /// `count` consecutive frames starting at `first`, e.g. one row of a grid sheet.
+    pub fn from_range(first: usize, count: usize, frame_duration: f32, mode: PlayMode) -> Animation {
+        Animation { frames: (first..first + count).map(|f| (f, frame_duration)).collect(), mode }
+    }
+
+    pub fn add_frame(&mut self, frame: usize, duration: f32) -> &mut Animation {
+        self.frames.push((frame, duration));
+        self
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Length of one pass through the frames in seconds.
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|(_, duration)| duration).sum()
+    }
+}
+
+/// Plays an `Animation`, call `update` with `FpsCap::dt` once per frame.
+pub struct AnimationPlayer {
+    animation: Animation,
+    index: usize,
+    elapsed: f32,
+    backwards: bool,
+    finished: bool,
+    /// Playback rate, 1.0 is normal speed.
+    pub speed: f32,
+    pub flip_horizontal: bool,
+    pub flip_vertical: bool,
+}
+
+impl AnimationPlayer {
+    pub fn new(animation: Animation) -> AnimationPlayer {
+        AnimationPlayer { animation, index: 0, elapsed: 0.0, backwards: false, finished: false, speed: 1.0, flip_horizontal: false, flip_vertical: false }
+    }
+
+    /// Switches to `animation` from its first frame, unless it is already playing.
+    pub fn play(&mut self, animation: &Animation) {
+        if self.animation != *animation {
+            self.animation = animation.clone();
+            self.reset();
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.index = 0;
+        self.elapsed = 0.0;
+        self.backwards = false;
+        self.finished = false;
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn animation(&self) -> &Animation {
+        &self.animation
+    }
+
+    /// Advances the animation by `dt` seconds scaled by `speed`.
+    pub fn update(&mut self, dt: f32) {
+        if self.finished || self.animation.is_empty() {
+            return;
+        }
+        self.elapsed += dt * self.speed;
+
+        let last = self.animation.len() - 1;
+        loop {
+            let duration = self.animation.frames[self.index].1;
+            // zero length frames would never let the loop end
+            if self.elapsed < duration || duration <= 0.0 {
+                break;
+            }
+            self.elapsed -= duration;
+
+            match self.animation.mode {
+                PlayMode::Loop => self.index = if self.index == last { 0 } else { self.index + 1 },
+                PlayMode::Once => {
+                    if self.index == last {
+                        self.finished = true;
+                        self.elapsed = 0.0;
+                        break;
+                    }
+                    self.index += 1;
+                }
+                PlayMode::PingPong => {
+                    if last == 0 {
+                        continue;
+                    }
+                    if self.backwards && self.index == 0 {
+                        self.backwards = false;
+                    } else if !self.backwards && self.index == last {
+                        self.backwards = true;
+                    }
+                    self.index = if self.backwards { self.index - 1 } else { self.index + 1 };
+                }
+            }
+        }
+    }
+
+    /// The sheet frame to show.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn frame(&self) -> usize {
+        self.animation.frames[self.index].0
+    }
+
+    /// True once a `PlayMode::Once` animation showed its last frame for its full duration.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Draws the current frame of `sheet` stretched over `dst`, flipped as set on the player.
+    pub fn draw(&self, renderer: &mut Renderer, sheet: &SpriteSheet, dst: Rect) {
+        if self.animation.is_empty() {
+            return;
+        }
+        sheet.draw(renderer, self.frame(), dst, self.flip_horizontal, self.flip_vertical);
+    }
+
+    /// Draws the current frame of `sheet` with its pivot on `position`, see `SpriteSheet::draw_at`.
+    pub fn draw_at(&self, renderer: &mut Renderer, sheet: &SpriteSheet, position: IVec2, scale: f32) {
+        if self.animation.is_empty() {
+            return;
+        }
+        sheet.draw_at(renderer, self.frame(), position, scale, self.flip_horizontal, self.flip_vertical);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        renderer::SoftwareCanvas,
+        textures::{PixelFormat, TextureAccess},
+    };
+
+    // the durations and steps are binary fractions, so the sums are exact
+
+    fn xywh(rect: Rect) -> (i32, i32, i32, i32) {
+        (rect.raw.x, rect.raw.y, rect.raw.w, rect.raw.h)
+    }
+
+    fn frames(player: &mut AnimationPlayer, dt: f32, steps: usize) -> Vec<usize> {
+        return (0..steps)
+            .map(|_| {
+                player.update(dt);
+                player.frame()
+            })
+            .collect();
+    }
+
+    #[test]
+    fn loop_wraps_around() {
+        let mut player = AnimationPlayer::new(Animation::from_frames(&[4, 5, 6], 0.25, PlayMode::Loop));
+        assert_eq!(player.frame(), 4);
+        assert_eq!(frames(&mut player, 0.125, 2), [4, 5]);
+        assert_eq!(frames(&mut player, 0.25, 4), [6, 4, 5, 6]);
+        assert!(!player.is_finished());
+    }
+
+    #[test]
+    fn ping_pong_bounces_at_both_ends() {
+        let mut player = AnimationPlayer::new(Animation::from_frames(&[1, 2, 3], 0.25, PlayMode::PingPong));
+        assert_eq!(frames(&mut player, 0.25, 7), [2, 3, 2, 1, 2, 3, 2]);
+
+        let mut single = AnimationPlayer::new(Animation::from_frames(&[7], 0.25, PlayMode::PingPong));
+        assert_eq!(frames(&mut single, 1.0, 2), [7, 7]);
+    }
+
+    #[test]
+    fn once_stops_on_the_last_frame() {
+        let mut player = AnimationPlayer::new(Animation::from_frames(&[1, 2], 0.25, PlayMode::Once));
+        player.update(0.25);
+        assert_eq!((player.frame(), player.is_finished()), (2, false));
+        player.update(0.25);
+        assert_eq!((player.frame(), player.is_finished()), (2, true));
+        player.update(10.0);
+        assert_eq!(player.frame(), 2);
+
+        player.reset();
+        assert_eq!((player.frame(), player.is_finished()), (1, false));
+    }
+
+    #[test]
+    fn dt_can_span_several_frames() {
+        let mut player = AnimationPlayer::new(Animation::from_range(10, 3, 0.25, PlayMode::Loop));
+        // four frames and a half: 10 11 12 10 | 11
+        player.update(1.125);
+        assert_eq!(player.frame(), 11);
+
+        let mut once = AnimationPlayer::new(Animation::from_range(10, 3, 0.25, PlayMode::Once));
+        once.update(5.0);
+        assert_eq!((once.frame(), once.is_finished()), (12, true));
+
+        let mut ping_pong = AnimationPlayer::new(Animation::from_range(10, 3, 0.25, PlayMode::PingPong));
+        // 10 11 12 11 10 | 11
+        ping_pong.update(1.25);
+        assert_eq!(ping_pong.frame(), 11);
+    }
+
+    #[test]
+    fn frames_keep_their_own_duration_scaled_by_speed() {
+        let mut animation = Animation::new(PlayMode::Loop);
+        animation.add_frame(0, 0.25).add_frame(1, 0.5);
+        assert_eq!(animation.duration(), 0.75);
+
+        let mut player = AnimationPlayer::new(animation);
+        assert_eq!(frames(&mut player, 0.25, 4), [1, 1, 0, 1]);
+
+        player.reset();
+        player.speed = 2.0;
+        assert_eq!(frames(&mut player, 0.125, 4), [1, 1, 0, 1]);
+        player.speed = 0.5;
+        assert_eq!(frames(&mut player, 0.25, 4), [1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn play_restarts_only_for_another_animation() {
+        let walk = Animation::from_frames(&[0, 1], 0.25, PlayMode::Loop);
+        let mut player = AnimationPlayer::new(walk.clone());
+        player.update(0.25);
+        player.play(&walk);
+        assert_eq!(player.frame(), 1);
+
+        player.play(&Animation::from_frames(&[5, 6], 0.25, PlayMode::Loop));
+        assert_eq!(player.frame(), 5);
+    }
+
+    #[test]
+    fn grid_frames_are_row_major() {
+        let canvas = SoftwareCanvas::new(8, 8);
+        let texture = |w, h| canvas.renderer.create_texture(PixelFormat::ARGB8888, TextureAccess::Static, w, h).unwrap();
+
+        // 100x50 in 32x32 cells: 4 columns, the last one cut off, and 2 rows
+        let sheet = SpriteSheet::from_grid(texture(100, 50), 32, 32).unwrap();
+        assert_eq!(sheet.len(), 8);
+        for row in 0..2 {
+            for column in 0..4 {
+                assert_eq!(xywh(sheet.frame(row * 4 + column)), (column as i32 * 32, row as i32 * 32, 32, 32));
+            }
+        }
+
+        assert!(SpriteSheet::from_grid(texture(100, 50), 0, 32).is_err());
+    }
+
+    /// The sheet must be dropped before `canvas`, which owns its texture.
+    fn import(canvas: &SoftwareCanvas, json: &str) -> ImportedSheet {
+        let texture = canvas.renderer.create_texture(PixelFormat::ARGB8888, TextureAccess::Static, 64, 64).unwrap();
+        return SpriteSheet::load_json_rw(texture, &mut RWops::from_bytes(json.as_bytes()).unwrap()).unwrap();
+    }
+
+    const ASEPRITE_ARRAY: &str = r#"{ "frames": [
+        { "filename": "baby 0.aseprite", "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "rotated": false, "trimmed": false,
+          "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 }, "sourceSize": { "w": 16, "h": 16 }, "duration": 100 },
+        { "filename": "baby 1.aseprite", "frame": { "x": 16, "y": 0, "w": 16, "h": 16 }, "rotated": false, "trimmed": false,
+          "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 }, "sourceSize": { "w": 16, "h": 16 }, "duration": 150 },
+        { "filename": "baby 2.aseprite", "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "rotated": false, "trimmed": false,
+          "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 }, "sourceSize": { "w": 16, "h": 16 }, "duration": 250 }
+      ],
+      "meta": { "app": "https://www.aseprite.org/", "image": "baby.png", "size": { "w": 48, "h": 16 },
+        "frameTags": [
+          { "name": "walk", "from": 0, "to": 2, "direction": "forward" },
+          { "name": "back", "from": 0, "to": 2, "direction": "reverse" },
+          { "name": "bounce", "from": 1, "to": 2, "direction": "pingpong" }
+        ],
+        "slices": [
+          { "name": "hitbox", "keys": [{ "frame": 0, "bounds": { "x": 2, "y": 2, "w": 12, "h": 12 } }] },
+          { "name": "feet", "keys": [{ "frame": 0, "bounds": { "x": 4, "y": 8, "w": 8, "h": 8 }, "pivot": { "x": 4, "y": 8 } }] }
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn aseprite_array_frames_tags_and_slice_pivot() {
+        let canvas = SoftwareCanvas::new(8, 8);
+        let imported = import(&canvas, ASEPRITE_ARRAY);
+        let sheet = &imported.sheet;
+
+        assert_eq!(imported.image.as_deref(), Some("baby.png"));
+        assert_eq!(sheet.len(), 3);
+        assert_eq!(sheet.frame_index("baby 2.aseprite"), Some(2));
+        assert_eq!(xywh(sheet.frame(1)), (16, 0, 16, 16));
+        assert!(!sheet.frame_info(1).is_trimmed());
+        assert_eq!(sheet.frame_info(1).duration, 0.15);
+
+        let walk = &imported.animations["walk"];
+        assert_eq!(walk.mode, PlayMode::Loop);
+        assert_eq!(walk.frames, [(0, 0.1), (1, 0.15), (2, 0.25)]);
+        assert_eq!(imported.animations["back"].frames, [(2, 0.25), (1, 0.15), (0, 0.1)]);
+        let bounce = &imported.animations["bounce"];
+        assert_eq!((bounce.mode, &bounce.frames[..]), (PlayMode::PingPong, &[(1, 0.15), (2, 0.25)][..]));
+
+        // the feet pivot (4 + 4, 8 + 8) of the 16x16 source, for every frame from 0 on
+        for index in 0..3 {
+            let pivot = sheet.frame_info(index).pivot;
+            assert_eq!((pivot.x, pivot.y), (0.5, 1.0));
+        }
+        let dst = Rect::new(10, 20, 32, 32);
+        assert_eq!(xywh(sheet.frame_info(0).trimmed_dst(dst, true, false)), xywh(dst));
+        let point = sheet.frame_info(0).pivot_point(dst, false, true);
+        assert_eq!((point.x, point.y), (26, 20));
+    }
+
+    const TEXTUREPACKER_HASH: &str = r#"{ "frames": {
+        "hero_idle.png": { "frame": { "x": 2, "y": 2, "w": 20, "h": 30 }, "rotated": false, "trimmed": true,
+          "spriteSourceSize": { "x": 6, "y": 2, "w": 20, "h": 30 }, "sourceSize": { "w": 32, "h": 32 }, "pivot": { "x": 0.5, "y": 0.875 } },
+        "hero_run.png": { "frame": { "x": 24, "y": 2, "w": 32, "h": 32 }, "rotated": false, "trimmed": false,
+          "spriteSourceSize": { "x": 0, "y": 0, "w": 32, "h": 32 }, "sourceSize": { "w": 32, "h": 32 } }
+      },
+      "animations": { "hero": ["hero_run.png", "hero_idle.png"] },
+      "meta": { "app": "https://www.codeandweb.com/texturepacker", "image": "hero.png", "size": { "w": 64, "h": 64 } }
+    }"#;
+
+    #[test]
+    fn texturepacker_hash_trimmed_frames_and_pivots() {
+        let canvas = SoftwareCanvas::new(8, 8);
+        let imported = import(&canvas, TEXTUREPACKER_HASH);
+        let sheet = &imported.sheet;
+
+        assert_eq!(imported.image.as_deref(), Some("hero.png"));
+        assert_eq!(sheet.frame_index("hero_idle.png"), Some(0));
+        assert_eq!(sheet.frame_index("hero_run.png"), Some(1));
+        let hero = &imported.animations["hero"];
+        assert_eq!(hero.mode, PlayMode::Loop);
+        assert_eq!(hero.frames, [(1, DEFAULT_FRAME_DURATION), (0, DEFAULT_FRAME_DURATION)]);
+
+        let idle = sheet.frame_info(0);
+        assert!(idle.is_trimmed());
+        assert_eq!(xywh(idle.rect), (2, 2, 20, 30));
+        assert_eq!((idle.offset.x, idle.offset.y, idle.source_size.x, idle.source_size.y), (6, 2, 32, 32));
+
+        // the 32x32 source drawn at twice its size
+        let dst = Rect::new(100, 100, 64, 64);
+        assert_eq!(xywh(idle.trimmed_dst(dst, false, false)), (112, 104, 40, 60));
+        assert_eq!(xywh(idle.trimmed_dst(dst, true, false)), (112, 104, 40, 60));
+        assert_eq!(xywh(idle.trimmed_dst(dst, false, true)), (112, 100, 40, 60));
+        let point = idle.pivot_point(dst, false, false);
+        assert_eq!((point.x, point.y), (132, 156));
+        let point = idle.pivot_point(dst, true, true);
+        assert_eq!((point.x, point.y), (132, 108));
+
+        let run = sheet.frame_info(1);
+        assert!(!run.is_trimmed());
+        assert_eq!((run.pivot.x, run.pivot.y), (0.5, 0.5));
+        assert_eq!(xywh(run.trimmed_dst(dst, false, false)), xywh(dst));
+    }
+
+    #[test]
+    fn import_rejects_rotated_frames_and_bad_references() {
+        let canvas = SoftwareCanvas::new(8, 8);
+        let load = |json: &str| {
+            let texture = canvas.renderer.create_texture(PixelFormat::ARGB8888, TextureAccess::Static, 64, 64).unwrap();
+            SpriteSheet::load_json_rw(texture, &mut RWops::from_bytes(json.as_bytes()).unwrap()).map(|_| ())
+        };
+
+        assert!(load(r#"{ "frames": { "a": { "frame": { "x": 0, "y": 0, "w": 8, "h": 8 }, "rotated": true } } }"#).is_err());
+        assert!(load(r#"{ "frames": { "a": { "frame": { "x": 0, "y": 0, "w": 8, "h": 8 } } }, "animations": { "x": ["b"] } }"#).is_err());
+        assert!(load(r#"{ "frames": [{ "frame": { "x": 0, "y": 0, "w": 8, "h": 8 } }], "meta": { "frameTags": [{ "name": "t", "from": 0, "to": 1 }] } }"#).is_err());
+        assert!(load(r#"{ "sprites": [] }"#).is_err());
+        assert!(load(r#"{ "frames": { "a": { "frame": { "x": 0, "y": 0, "w": 8, "h": 8 } } } }"#).is_ok());
+    }
+}
@@ -2,7 +2,7 @@ use anyhow;
 use sdl2_sys as sdl;
 use std::path::Path;
 
-use crate::rwops::RWops;
+use crate::{core::Rect, rwops::RWops};
 
 pub struct Surface {
     pub raw: *mut sdl::SDL_Surface,
@@ -17,6 +17,74 @@ impl Drop for Surface {
 }
 
 impl Surface {
+    /// A transparent 32 bit RGBA surface.
+    #[doc(alias = "SDL_CreateRGBSurfaceWithFormat")]
+    pub fn new(width: i32, height: i32) -> anyhow::Result<Surface> {
+        let raw = unsafe { sdl::SDL_CreateRGBSurfaceWithFormat(0, width, height, 32, sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_RGBA32 as u32) };
+
+        if raw.is_null() {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        } else {
+            return Ok(Surface { raw });
+        }
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn width(&self) -> i32 {
+        unsafe { (*self.raw).w }
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn height(&self) -> i32 {
+        unsafe { (*self.raw).h }
+    }
+
+    /// Copies `src_rect` of the surface, or all of it if it is `None`, to `x`, `y` of `dst`.
+    ///
+    /// With `blend` false the pixels, alpha included, replace those of `dst`. The blend mode of the
+    /// surface is set for the copy only, the previous one is restored afterwards.
+    #[doc(alias = "SDL_BlitSurface")]
+    pub fn blit(&self, src_rect: Option<Rect>, dst: &mut Surface, x: i32, y: i32, blend: bool) -> anyhow::Result<()> {
+        let blend_mode = if blend { sdl::SDL_BlendMode::SDL_BLENDMODE_BLEND } else { sdl::SDL_BlendMode::SDL_BLENDMODE_NONE };
+        let src_rect = src_rect.map(|r| r.raw);
+        let mut dst_rect = sdl::SDL_Rect { x, y, w: 0, h: 0 };
+
+        unsafe {
+            let mut previous = sdl::SDL_BlendMode::SDL_BLENDMODE_NONE;
+            if sdl::SDL_GetSurfaceBlendMode(self.raw, &mut previous) != 0 || sdl::SDL_SetSurfaceBlendMode(self.raw, blend_mode) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+
+            let src_ptr = src_rect.as_ref().map_or(std::ptr::null(), |r| r as *const _);
+            let res = sdl::SDL_UpperBlit(self.raw, src_ptr, dst.raw, &mut dst_rect);
+            // read the error before restoring, which could overwrite it
+            let error = if res != 0 { Some(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) } else { None };
+            sdl::SDL_SetSurfaceBlendMode(self.raw, previous);
+
+            if let Some(error) = error {
+                return Err(error);
+            }
+        }
+
+        return Ok(());
+    }
+
+    #[doc(alias = "IMG_SavePNG_RW")]
+    pub fn save_png_rw(&self, rwops: &mut RWops) -> anyhow::Result<()> {
+        let res = unsafe { sdl::image::IMG_SavePNG_RW(self.raw, rwops.raw, 0) };
+
+        if res != 0 {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        }
+
+        return Ok(());
+    }
+
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
+        let mut file = RWops::from_file(path, "wb\0")?;
+        return self.save_png_rw(&mut file);
+    }
+
     pub fn load_bmp_rw(rwops: &mut RWops) -> anyhow::Result<Surface> {
         let raw = unsafe { sdl::SDL_LoadBMP_RW(rwops.raw, 0) };
 
@@ -31,4 +99,20 @@ impl Surface {
         let mut file = RWops::from_file(path, "rb\0")?;
         return  Surface::load_bmp_rw(&mut file);
     }
+
+    /// Loads any format supported by SDL2_image (png, jpeg, ...), see `SDLContext::init_image`.
+    pub fn load_image_rw(rwops: &mut RWops) -> anyhow::Result<Surface> {
+        let raw = unsafe { sdl::image::IMG_Load_RW(rwops.raw, 0) };
+
+        if raw.is_null() {
+            return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+        } else {
+            return Ok(Surface { raw });
+        }
+    }
+
+    pub fn load_image<P: AsRef<Path>>(path: P) -> anyhow::Result<Surface> {
+        let mut file = RWops::from_file(path, "rb\0")?;
+        return Surface::load_image_rw(&mut file);
+    }
 }
@@ -0,0 +1,299 @@
+use std::f32::consts::PI;
+
+use anyhow;
+
+use crate::audio::{AudioBuffer, AudioFormat, AudioSpec};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Waveform {
+    Sine,
+    Square,
+    /// Square wave that is high for the given fraction of each period, e.g. 0.125 or 0.25.
+    Pulse(f32),
+    Saw,
+    Triangle,
+    /// White noise, the frequency is ignored.
+    Noise,
+}
+
+/// Attack, decay and release times in seconds, `sustain` is the level held in between.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Envelope {
+    pub attack: f32,
+    pub decay: f32,
+    pub sustain: f32,
+    pub release: f32,
+}
+
+impl Envelope {
+    pub fn new(attack: f32, decay: f32, sustain: f32, release: f32) -> Envelope {
+        Envelope { attack, decay, sustain, release }
+    }
+
+    /// Level of a note pressed for `held` seconds, `t` seconds after it started.
+    pub fn gain(&self, t: f32, held: f32) -> f32 {
+        let level = |t: f32| {
+            if t < self.attack {
+                t / self.attack
+            } else if t < self.attack + self.decay {
+                1.0 - (1.0 - self.sustain) * (t - self.attack) / self.decay
+            } else {
+                self.sustain
+            }
+        };
+
+        if t < held {
+            return level(t);
+        }
+        if self.release <= 0.0 {
+            return 0.0;
+        }
+        return level(held) * (1.0 - (t - held) / self.release).max(0.0);
+    }
+}
+
+/// Renders a single procedural sound, e.g. a jump or coin SFX.
+///
+/// ```ignore
+/// let coin = Synth::new(Waveform::Square)
+///     .frequency(988.0)
+///     .sweep_to(1319.0)
+///     .duration(0.15)
+///     .envelope(Envelope::new(0.0, 0.05, 0.6, 0.05))
+///     .render(22050)?;
+/// coin.save_wav(&mut RWops::from_file("coin.wav\0", "wb\0")?)?;
+/// ```
+///
+/// Rendering is deterministic, the noise uses its own seeded generator.
+pub struct Synth {
+    waveform: Waveform,
+    frequency: f32,
+    sweep_to: Option<f32>,
+    duration: f32,
+    envelope: Option<Envelope>,
+    low_pass: Option<f32>,
+    volume: f32,
+    seed: u32,
+}
+
+impl Synth {
+    pub fn new(waveform: Waveform) -> Synth {
+        Synth { waveform, frequency: 440.0, sweep_to: None, duration: 1.0, envelope: None, low_pass: None, volume: 1.0, seed: 0x1234_5678 }
+    }
+
+    /// Start frequency in Hz.
+    pub fn frequency(&mut self, frequency: f32) -> &mut Synth {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Slides the frequency linearly to `frequency` over the whole sound.
+    pub fn sweep_to(&mut self, frequency: f32) -> &mut Synth {
+        self.sweep_to = Some(frequency);
+        self
+    }
+
+    /// Length in seconds, the envelope release is added on top.
+    pub fn duration(&mut self, seconds: f32) -> &mut Synth {
+        self.duration = seconds.max(0.0);
+        self
+    }
+
+    pub fn envelope(&mut self, envelope: Envelope) -> &mut Synth {
+        self.envelope = Some(envelope);
+        self
+    }
+
+    /// One pole low-pass filter with the cutoff frequency in Hz.
+    pub fn low_pass(&mut self, cutoff: f32) -> &mut Synth {
+        self.low_pass = Some(cutoff);
+        self
+    }
+
+    /// Peak amplitude from 0.0 to 1.0.
+    pub fn volume(&mut self, volume: f32) -> &mut Synth {
+        self.volume = volume.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn seed(&mut self, seed: u32) -> &mut Synth {
+        // xorshift is stuck at zero
+        self.seed = seed.max(1);
+        self
+    }
+
+    /// Total length in seconds, including the release.
+    pub fn length(&self) -> f32 {
+        self.duration + self.envelope.map_or(0.0, |e| e.release.max(0.0))
+    }
+
+    /// Mono samples in `-1.0..=1.0` at `sample_rate`.
+    pub fn render_samples(&self, sample_rate: i32) -> Vec<f32> {
+        let rate = sample_rate as f32;
+        let frames = (self.length() * rate).round() as usize;
+        let alpha = self.low_pass.map(|cutoff| {
+            let rc = 1.0 / (2.0 * PI * cutoff.max(1.0));
+            let dt = 1.0 / rate;
+            dt / (rc + dt)
+        });
+
+        let mut samples = Vec::with_capacity(frames);
+        let mut phase = 0.0f32;
+        let mut noise = self.seed;
+        let mut filtered = 0.0f32;
+
+        for frame in 0..frames {
+            let t = frame as f32 / rate;
+
+            let mut sample = match self.waveform {
+                Waveform::Sine => (2.0 * PI * phase).sin(),
+                Waveform::Square => if phase < 0.5 { 1.0 } else { -1.0 },
+                Waveform::Pulse(duty) => if phase < duty { 1.0 } else { -1.0 },
+                Waveform::Saw => 2.0 * phase - 1.0,
+                Waveform::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+                Waveform::Noise => {
+                    noise ^= noise << 13;
+                    noise ^= noise >> 17;
+                    noise ^= noise << 5;
+                    noise as f32 / u32::MAX as f32 * 2.0 - 1.0
+                }
+            };
+
+            if let Some(alpha) = alpha {
+                filtered += alpha * (sample - filtered);
+                sample = filtered;
+            }
+            if let Some(envelope) = self.envelope {
+                sample *= envelope.gain(t, self.duration);
+            }
+            samples.push(sample * self.volume);
+
+            let frequency = match self.sweep_to {
+                Some(end) if self.duration > 0.0 => self.frequency + (end - self.frequency) * (t / self.duration).min(1.0),
+                _ => self.frequency,
+            };
+            phase = (phase + frequency / rate).fract();
+        }
+
+        return samples;
+    }
+
+    /// Renders into a mono signed 16 bit buffer, the format of `assets/sine.wav`.
+    pub fn render(&self, sample_rate: i32) -> anyhow::Result<AudioBuffer> {
+        let samples: Vec<i16> = self.render_samples(sample_rate).iter().map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16).collect();
+        let spec = AudioSpec::new(sample_rate, AudioFormat::s16_sys(), 1, 4096);
+
+        return AudioBuffer::from_samples(spec, &samples);
+    }
+
+    /// Renders into a mono f32 buffer.
+    pub fn render_f32(&self, sample_rate: i32) -> anyhow::Result<AudioBuffer> {
+        let spec = AudioSpec::new(sample_rate, AudioFormat::f32_sys(), 1, 4096);
+
+        return AudioBuffer::from_samples(spec, &self.render_samples(sample_rate));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the rates and frequencies below step the phase in exact binary fractions, so the samples are exact too
+
+    #[test]
+    fn square_renders_full_scale_halves() {
+        let buffer = Synth::new(Waveform::Square).frequency(1000.0).duration(0.001).render(8000).unwrap();
+
+        assert_eq!(buffer.samples::<i16>().unwrap(), &[32767, 32767, 32767, 32767, -32767, -32767, -32767, -32767]);
+        assert_eq!(buffer.frames(), 8);
+        assert_eq!(buffer.duration_ms(), 1);
+    }
+
+    #[test]
+    fn sine_hits_the_peaks_and_zero_crossings() {
+        let buffer = Synth::new(Waveform::Sine).frequency(2000.0).duration(0.001).render(8000).unwrap();
+
+        assert_eq!(buffer.samples::<i16>().unwrap(), &[0, 32767, 0, -32767, 0, 32767, 0, -32767]);
+    }
+
+    #[test]
+    fn saw_and_triangle_ramps() {
+        let saw = Synth::new(Waveform::Saw).frequency(2000.0).duration(0.0005).render_f32(8000).unwrap();
+        assert_eq!(saw.samples::<f32>().unwrap(), &[-1.0, -0.5, 0.0, 0.5]);
+
+        let triangle = Synth::new(Waveform::Triangle).frequency(1000.0).duration(0.001).render_samples(8000);
+        assert_eq!(triangle, [-1.0, -0.5, 0.0, 0.5, 1.0, 0.5, 0.0, -0.5]);
+    }
+
+    #[test]
+    fn volume_scales_the_peak() {
+        let buffer = Synth::new(Waveform::Square).frequency(1000.0).duration(0.001).volume(0.5).render(8000).unwrap();
+        let samples = buffer.samples::<i16>().unwrap();
+
+        assert_eq!(samples.iter().max(), Some(&16384));
+        assert_eq!(samples.iter().min(), Some(&-16384));
+    }
+
+    #[test]
+    fn envelope_attack_decay_sustain_release() {
+        // a 100% pulse is a constant 1.0, so the output is the envelope itself
+        let samples = Synth::new(Waveform::Pulse(1.0)).duration(1.0).envelope(Envelope::new(0.25, 0.25, 0.5, 0.5)).render_samples(8);
+
+        assert_eq!(samples.len(), 12);
+        // attack
+        assert_eq!(samples[..2], [0.0, 0.5]);
+        // decay, peaking where the attack ends
+        assert_eq!(samples[2..4], [1.0, 0.75]);
+        // sustain up to the end of the duration
+        assert_eq!(samples[4..8], [0.5, 0.5, 0.5, 0.5]);
+        // release from the sustain level
+        assert_eq!(samples[8..], [0.5, 0.375, 0.25, 0.125]);
+    }
+
+    #[test]
+    fn release_is_added_to_the_length() {
+        let mut synth = Synth::new(Waveform::Sine);
+        synth.duration(0.5).envelope(Envelope::new(0.0, 0.0, 1.0, 0.25));
+
+        assert_eq!(synth.length(), 0.75);
+        assert_eq!(synth.render_samples(1000).len(), 750);
+        assert_eq!(synth.render(1000).unwrap().duration_ms(), 750);
+    }
+
+    #[test]
+    fn noise_is_seeded() {
+        let mut synth = Synth::new(Waveform::Noise);
+        synth.duration(0.01);
+        let first = synth.render_samples(44100);
+
+        assert_eq!(first.len(), 441);
+        assert_eq!(synth.render_samples(44100), first);
+        assert!(first.iter().all(|s| (-1.0..=1.0).contains(s)));
+        assert_ne!(synth.seed(7).render_samples(44100), first);
+    }
+
+    #[test]
+    fn save_wav_writes_the_exact_bytes_and_loads_back() {
+        let tone = Synth::new(Waveform::Square).frequency(1000.0).duration(0.001).render(8000).unwrap();
+        let mut file = [0u8; 60];
+        let mut rwops = crate::rwops::RWops::from_bytes_mut(&mut file).unwrap();
+        tone.save_wav(&mut rwops).unwrap();
+        drop(rwops);
+
+        #[rustfmt::skip]
+        let expected: [u8; 60] = [
+            b'R', b'I', b'F', b'F', 52, 0, 0, 0, b'W', b'A', b'V', b'E',
+            // fmt: pcm, mono, 8000 Hz, 16000 bytes/s, 2 byte frames, 16 bits
+            b'f', b'm', b't', b' ', 16, 0, 0, 0, 1, 0, 1, 0, 0x40, 0x1f, 0, 0, 0x80, 0x3e, 0, 0, 2, 0, 16, 0,
+            b'd', b'a', b't', b'a', 16, 0, 0, 0,
+            0xff, 0x7f, 0xff, 0x7f, 0xff, 0x7f, 0xff, 0x7f, 0x01, 0x80, 0x01, 0x80, 0x01, 0x80, 0x01, 0x80,
+        ];
+        assert_eq!(file, expected);
+
+        let loaded = AudioBuffer::load_wav_rw(&mut crate::rwops::RWops::from_bytes(&file).unwrap()).unwrap();
+        assert_eq!(loaded.spec().freq, 8000);
+        assert_eq!(loaded.spec().channels, 1);
+        assert_eq!(loaded.spec().format, AudioFormat::S16LSB);
+        assert_eq!(loaded.samples::<i16>(), tone.samples::<i16>());
+    }
+}
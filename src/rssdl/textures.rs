@@ -1,5 +1,198 @@
+use anyhow;
 use sdl2_sys as sdl;
 
+use crate::core::Rect;
+
+/// Texture pixel formats, the names give the order of the channels from the most to the
+/// least significant bits of a pixel. Use `rgba32` for bytes in R, G, B, A order.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PixelFormat {
+    Unknown,
+    Index8,
+    RGB332,
+    RGB565,
+    BGR565,
+    ARGB4444,
+    RGBA4444,
+    ARGB1555,
+    RGB24,
+    BGR24,
+    XRGB8888,
+    XBGR8888,
+    ARGB8888,
+    RGBA8888,
+    ABGR8888,
+    BGRA8888,
+    ARGB2101010,
+    /// Planar Y, V, U.
+    YV12,
+    /// Planar Y, U, V.
+    IYUV,
+    YUY2,
+    UYVY,
+    YVYU,
+    /// Planar Y, then interleaved U and V.
+    NV12,
+    /// Planar Y, then interleaved V and U.
+    NV21,
+}
+
+impl PixelFormat {
+    const ALL: [PixelFormat; 24] = [
+        PixelFormat::Unknown,
+        PixelFormat::Index8,
+        PixelFormat::RGB332,
+        PixelFormat::RGB565,
+        PixelFormat::BGR565,
+        PixelFormat::ARGB4444,
+        PixelFormat::RGBA4444,
+        PixelFormat::ARGB1555,
+        PixelFormat::RGB24,
+        PixelFormat::BGR24,
+        PixelFormat::XRGB8888,
+        PixelFormat::XBGR8888,
+        PixelFormat::ARGB8888,
+        PixelFormat::RGBA8888,
+        PixelFormat::ABGR8888,
+        PixelFormat::BGRA8888,
+        PixelFormat::ARGB2101010,
+        PixelFormat::YV12,
+        PixelFormat::IYUV,
+        PixelFormat::YUY2,
+        PixelFormat::UYVY,
+        PixelFormat::YVYU,
+        PixelFormat::NV12,
+        PixelFormat::NV21,
+    ];
+
+    /// `None` for formats without a variant, e.g. the 1 and 4 bit indexed ones.
+    pub fn from_ll(format: u32) -> Option<PixelFormat> {
+        PixelFormat::ALL.iter().copied().find(|f| f.to_ll() as u32 == format)
+    }
+
+    pub fn to_ll(self) -> sdl::SDL_PixelFormatEnum {
+        match self {
+            PixelFormat::Unknown => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_UNKNOWN,
+            PixelFormat::Index8 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_INDEX8,
+            PixelFormat::RGB332 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_RGB332,
+            PixelFormat::RGB565 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_RGB565,
+            PixelFormat::BGR565 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_BGR565,
+            PixelFormat::ARGB4444 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_ARGB4444,
+            PixelFormat::RGBA4444 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_RGBA4444,
+            PixelFormat::ARGB1555 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_ARGB1555,
+            PixelFormat::RGB24 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_RGB24,
+            PixelFormat::BGR24 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_BGR24,
+            PixelFormat::XRGB8888 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_XRGB8888,
+            PixelFormat::XBGR8888 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_XBGR8888,
+            PixelFormat::ARGB8888 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_ARGB8888,
+            PixelFormat::RGBA8888 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_RGBA8888,
+            PixelFormat::ABGR8888 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_ABGR8888,
+            PixelFormat::BGRA8888 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_BGRA8888,
+            PixelFormat::ARGB2101010 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_ARGB2101010,
+            PixelFormat::YV12 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_YV12,
+            PixelFormat::IYUV => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_IYUV,
+            PixelFormat::YUY2 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_YUY2,
+            PixelFormat::UYVY => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_UYVY,
+            PixelFormat::YVYU => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_YVYU,
+            PixelFormat::NV12 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_NV12,
+            PixelFormat::NV21 => sdl::SDL_PixelFormatEnum::SDL_PIXELFORMAT_NV21,
+        }
+    }
+
+    /// Bytes R, G, B, A in memory order.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn rgba32() -> PixelFormat {
+        if cfg!(target_endian = "little") { PixelFormat::ABGR8888 } else { PixelFormat::RGBA8888 }
+    }
+
+    /// Bytes A, R, G, B in memory order.
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub const fn argb32() -> PixelFormat {
+        if cfg!(target_endian = "little") { PixelFormat::BGRA8888 } else { PixelFormat::ARGB8888 }
+    }
+
+    /// Bytes per pixel, for planar formats those of the Y plane.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Unknown => 0,
+            PixelFormat::Index8 | PixelFormat::RGB332 => 1,
+            PixelFormat::RGB565 | PixelFormat::BGR565 | PixelFormat::ARGB4444 | PixelFormat::RGBA4444 | PixelFormat::ARGB1555 => 2,
+            PixelFormat::RGB24 | PixelFormat::BGR24 => 3,
+            PixelFormat::XRGB8888 | PixelFormat::XBGR8888 | PixelFormat::ARGB8888 | PixelFormat::RGBA8888 | PixelFormat::ABGR8888 | PixelFormat::BGRA8888 | PixelFormat::ARGB2101010 => 4,
+            PixelFormat::YUY2 | PixelFormat::UYVY | PixelFormat::YVYU => 2,
+            PixelFormat::YV12 | PixelFormat::IYUV | PixelFormat::NV12 | PixelFormat::NV21 => 1,
+        }
+    }
+
+    #[cfg_attr(not(debug_assertions), inline(always))]
+    pub fn is_planar(self) -> bool {
+        matches!(self, PixelFormat::YV12 | PixelFormat::IYUV | PixelFormat::NV12 | PixelFormat::NV21)
+    }
+
+    /// Smallest buffer holding `width` x `height` pixels with rows `pitch` bytes apart,
+    /// for planar formats the half size chroma planes follow the Y plane.
+    pub fn buffer_len(self, width: usize, height: usize, pitch: usize) -> usize {
+        if height == 0 {
+            return 0;
+        }
+        if self.is_planar() {
+            return pitch * height + 2 * pitch.div_ceil(2) * height.div_ceil(2);
+        }
+
+        return pitch * (height - 1) + width * self.bytes_per_pixel();
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TextureAccess {
+    /// Changes rarely, set the pixels with `Texture::update`.
+    Static,
+    /// Changes often, set the pixels with `Texture::with_lock` or `Texture::update`.
+    Streaming,
+    /// Can be rendered to.
+    Target,
+}
+
+impl TextureAccess {
+    pub fn from_ll(access: i32) -> Option<TextureAccess> {
+        let access = match access {
+            0 => TextureAccess::Static,
+            1 => TextureAccess::Streaming,
+            2 => TextureAccess::Target,
+            _ => return None,
+        };
+
+        return Some(access);
+    }
+
+    pub fn to_ll(self) -> sdl::SDL_TextureAccess {
+        match self {
+            TextureAccess::Static => sdl::SDL_TextureAccess::SDL_TEXTUREACCESS_STATIC,
+            TextureAccess::Streaming => sdl::SDL_TextureAccess::SDL_TEXTUREACCESS_STREAMING,
+            TextureAccess::Target => sdl::SDL_TextureAccess::SDL_TEXTUREACCESS_TARGET,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TextureQuery {
+    pub format: PixelFormat,
+    pub access: TextureAccess,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Unlocks the texture even if the `with_lock` closure panics.
+struct TextureLock {
+    raw: *mut sdl::SDL_Texture,
+}
+
+impl Drop for TextureLock {
+    fn drop(&mut self) {
+        unsafe { sdl::SDL_UnlockTexture(self.raw) };
+    }
+}
+
 pub struct Texture {
     pub raw: *mut sdl::SDL_Texture,
 }
@@ -24,4 +217,109 @@ impl Texture {
             }
         }
     }
+
+    #[doc(alias = "SDL_QueryTexture")]
+    pub fn query(&self) -> anyhow::Result<TextureQuery> {
+        let (mut format, mut access, mut width, mut height) = (0, 0, 0, 0);
+        unsafe {
+            if sdl::SDL_QueryTexture(self.raw, &mut format, &mut access, &mut width, &mut height) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(TextureQuery {
+            format: PixelFormat::from_ll(format).unwrap_or(PixelFormat::Unknown),
+            access: TextureAccess::from_ll(access).unwrap_or(TextureAccess::Static),
+            width,
+            height,
+        });
+    }
+
+    /// The size of `rect`, or of the whole texture if it is `None`. Errors if `rect` is not
+    /// inside the texture, the pixel buffers are sized from it.
+    fn area(&self, rect: Option<Rect>) -> anyhow::Result<(TextureQuery, usize, usize)> {
+        let query = self.query()?;
+        let rect = match rect {
+            Some(rect) => rect.raw,
+            None => return Ok((query, query.width.max(0) as usize, query.height.max(0) as usize)),
+        };
+
+        let inside = rect.x >= 0
+            && rect.y >= 0
+            && rect.w >= 0
+            && rect.h >= 0
+            && rect.x as i64 + rect.w as i64 <= query.width as i64
+            && rect.y as i64 + rect.h as i64 <= query.height as i64;
+        if !inside {
+            return Err(anyhow::anyhow!("Rect ({}, {}, {}x{}) is not inside the {}x{} texture {}({},{})", rect.x, rect.y, rect.w, rect.h, query.width, query.height, file!(), line!(), column!()));
+        }
+
+        return Ok((query, rect.w as usize, rect.h as usize));
+    }
+
+    /// Replaces the pixels of `rect`, or of the whole texture if it is `None`, with `pixels`
+    /// in the texture format, rows `pitch` bytes apart. `rect` must be inside the texture.
+    /// Slow, use `with_lock` on streaming textures that change every frame.
+    #[doc(alias = "SDL_UpdateTexture")]
+    pub fn update(&mut self, rect: Option<Rect>, pixels: &[u8], pitch: usize) -> anyhow::Result<()> {
+        let (query, width, height) = self.area(rect)?;
+        let needed = query.format.buffer_len(width, height, pitch);
+        if pixels.len() < needed {
+            return Err(anyhow::anyhow!("{} bytes of pixels, {:?} {}x{} with pitch {} needs {} {}({},{})", pixels.len(), query.format, width, height, pitch, needed, file!(), line!(), column!()));
+        }
+
+        let rect = rect.map(|r| r.raw);
+        unsafe {
+            let rect_ptr = rect.as_ref().map_or(std::ptr::null(), |r| r as *const _);
+            if sdl::SDL_UpdateTexture(self.raw, rect_ptr, pixels.as_ptr() as *const _, pitch as i32) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// Locks `rect`, or the whole texture if it is `None`, and passes its pixels and pitch
+    /// to `f`. Only for `TextureAccess::Streaming` textures. The old pixels are not kept,
+    /// `f` has to write every pixel.
+    ///
+    /// `rect` must be inside the texture. Planar formats can only be locked whole, the
+    /// chroma rows of a part are not contiguous with its Y rows.
+    ///
+    /// ```ignore
+    /// let mut texture = renderer.create_texture(PixelFormat::rgba32(), TextureAccess::Streaming, 256, 256)?;
+    /// texture.with_lock(None, |pixels, pitch| {
+    ///     for (y, row) in pixels.chunks_mut(pitch).enumerate() {
+    ///         for (x, pixel) in row[..256 * 4].chunks_exact_mut(4).enumerate() {
+    ///             pixel.copy_from_slice(&[x as u8, y as u8, 0, 255]);
+    ///         }
+    ///     }
+    /// })?;
+    /// ```
+    #[doc(alias = "SDL_LockTexture")]
+    pub fn with_lock<F, R>(&mut self, rect: Option<Rect>, f: F) -> anyhow::Result<R>
+    where
+        F: FnOnce(&mut [u8], usize) -> R,
+    {
+        let (query, width, height) = self.area(rect)?;
+        if rect.is_some() && query.format.is_planar() {
+            return Err(anyhow::anyhow!("Can't lock part of a planar {:?} texture {}({},{})", query.format, file!(), line!(), column!()));
+        }
+
+        let rect = rect.map(|r| r.raw);
+        let mut pixels = std::ptr::null_mut();
+        let mut pitch = 0;
+
+        unsafe {
+            let rect_ptr = rect.as_ref().map_or(std::ptr::null(), |r| r as *const _);
+            if sdl::SDL_LockTexture(self.raw, rect_ptr, &mut pixels, &mut pitch) != 0 {
+                return Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!()));
+            }
+            let _lock = TextureLock { raw: self.raw };
+
+            let pitch = pitch as usize;
+            let pixels = std::slice::from_raw_parts_mut(pixels as *mut u8, query.format.buffer_len(width, height, pitch));
+            return Ok(f(pixels, pitch));
+        }
+    }
 }
@@ -0,0 +1,138 @@
+use anyhow;
+use sdl2_sys as sdl;
+
+use crate::rwops::RWops;
+
+pub type TouchId = i64;
+pub type FingerId = i64;
+pub type GestureId = i64;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TouchDeviceType {
+    Invalid,
+    /// Touch screen, coordinates match the window.
+    Direct,
+    /// Trackpad with absolute coordinates.
+    IndirectAbsolute,
+    /// Trackpad with screen cursor relative coordinates.
+    IndirectRelative,
+}
+
+impl TouchDeviceType {
+    pub fn from_ll(type_: sdl::SDL_TouchDeviceType) -> TouchDeviceType {
+        match type_ {
+            sdl::SDL_TouchDeviceType::SDL_TOUCH_DEVICE_INVALID => TouchDeviceType::Invalid,
+            sdl::SDL_TouchDeviceType::SDL_TOUCH_DEVICE_DIRECT => TouchDeviceType::Direct,
+            sdl::SDL_TouchDeviceType::SDL_TOUCH_DEVICE_INDIRECT_ABSOLUTE => TouchDeviceType::IndirectAbsolute,
+            sdl::SDL_TouchDeviceType::SDL_TOUCH_DEVICE_INDIRECT_RELATIVE => TouchDeviceType::IndirectRelative,
+        }
+    }
+}
+
+/// A finger currently touching a device, `x` and `y` are normalized to `0.0..=1.0`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Finger {
+    pub id: FingerId,
+    pub x: f32,
+    pub y: f32,
+    pub pressure: f32,
+}
+
+#[doc(alias = "SDL_GetNumTouchDevices")]
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub fn num_touch_devices() -> u32 {
+    unsafe { sdl::SDL_GetNumTouchDevices().max(0) as u32 }
+}
+
+/// The id of the touch device at `index`, as found in the `touch_id` of finger events.
+#[doc(alias = "SDL_GetTouchDevice")]
+pub fn touch_device(index: u32) -> anyhow::Result<TouchId> {
+    let touch_id = unsafe { sdl::SDL_GetTouchDevice(index as i32) };
+
+    if touch_id == 0 {
+        return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+    }
+
+    return Ok(touch_id);
+}
+
+#[doc(alias = "SDL_GetTouchDeviceType")]
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub fn touch_device_type(touch_id: TouchId) -> TouchDeviceType {
+    TouchDeviceType::from_ll(unsafe { sdl::SDL_GetTouchDeviceType(touch_id) })
+}
+
+/// Number of fingers currently on the device, 0 for an unknown `touch_id`.
+#[doc(alias = "SDL_GetNumTouchFingers")]
+#[cfg_attr(not(debug_assertions), inline(always))]
+pub fn num_touch_fingers(touch_id: TouchId) -> u32 {
+    unsafe { sdl::SDL_GetNumTouchFingers(touch_id).max(0) as u32 }
+}
+
+#[doc(alias = "SDL_GetTouchFinger")]
+pub fn touch_finger(touch_id: TouchId, index: u32) -> Option<Finger> {
+    unsafe {
+        let finger = sdl::SDL_GetTouchFinger(touch_id, index as i32);
+        if finger.is_null() {
+            return None;
+        }
+        let finger = *finger;
+        return Some(Finger { id: finger.id, x: finger.x, y: finger.y, pressure: finger.pressure });
+    }
+}
+
+/// Every finger currently on the device.
+pub fn touch_fingers(touch_id: TouchId) -> Vec<Finger> {
+    (0..num_touch_fingers(touch_id)).filter_map(|index| touch_finger(touch_id, index)).collect()
+}
+
+/// Starts recording a dollar gesture on `touch_id`, or on every device if it is `None`.
+///
+/// The next gesture drawn is stored as a template and reported with `Event::DollarRecord`,
+/// later matches are reported with `Event::DollarGesture`.
+#[doc(alias = "SDL_RecordGesture")]
+pub fn record_gesture(touch_id: Option<TouchId>) -> anyhow::Result<()> {
+    let res = unsafe { sdl::SDL_RecordGesture(touch_id.unwrap_or(-1)) };
+
+    if res == 0 {
+        return Err(anyhow::anyhow!("Touch device {:?} not found {}({},{})", touch_id, file!(), line!(), column!()));
+    }
+
+    return Ok(());
+}
+
+/// Writes every recorded template, returns the number of saved templates, 0 if none were recorded.
+#[doc(alias = "SDL_SaveAllDollarTemplates")]
+pub fn save_all_dollar_templates(rwops: &mut RWops) -> anyhow::Result<u32> {
+    let res = unsafe { sdl::SDL_SaveAllDollarTemplates(rwops.raw) };
+
+    if res < 0 {
+        return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+    }
+
+    return Ok(res as u32);
+}
+
+#[doc(alias = "SDL_SaveDollarTemplate")]
+pub fn save_dollar_template(gesture_id: GestureId, rwops: &mut RWops) -> anyhow::Result<()> {
+    let res = unsafe { sdl::SDL_SaveDollarTemplate(gesture_id, rwops.raw) };
+
+    if res <= 0 {
+        return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+    }
+
+    return Ok(());
+}
+
+/// Loads templates saved with `save_all_dollar_templates` or `save_dollar_template` for
+/// `touch_id`, or for every device if it is `None`. Returns the number of loaded templates.
+#[doc(alias = "SDL_LoadDollarTemplates")]
+pub fn load_dollar_templates(touch_id: Option<TouchId>, rwops: &mut RWops) -> anyhow::Result<u32> {
+    let res = unsafe { sdl::SDL_LoadDollarTemplates(touch_id.unwrap_or(-1), rwops.raw) };
+
+    if res < 0 {
+        return unsafe { Err(anyhow::anyhow!("{:?} {}({},{})", std::ffi::CStr::from_ptr(sdl::SDL_GetError()), file!(), line!(), column!())) };
+    }
+
+    return Ok(res as u32);
+}